@@ -0,0 +1,7 @@
+extern crate clap;
+extern crate itertools;
+
+pub mod parser;
+mod test_utils;
+pub mod tree;
+pub mod writer;
@@ -1,17 +1,37 @@
+extern crate astree;
 extern crate clap;
-extern crate itertools;
 
-use crate::parser::parse;
+use astree::parser::parse;
 
-use crate::tree::style::BoxDrawings;
-use crate::tree::style::Style;
-use crate::tree::vertical::render;
-use clap::{Parser, Subcommand};
-use tree::horizontal;
-
-mod parser;
-mod test_utils;
-mod tree;
+use astree::tree::color;
+use astree::tree::color::{ColorBy, ColorWhen};
+use astree::tree::fit;
+use astree::tree::horizontal;
+use astree::tree::horizontal_boxes;
+use astree::tree::mindmap;
+use astree::tree::panels;
+use astree::tree::style::BoxDrawings;
+use astree::tree::style::GuideLines;
+use astree::tree::style::Style;
+use astree::tree::tree_node::TreeNode;
+use astree::tree::vertical;
+use astree::tree::vertical::render;
+use astree::writer;
+use astree::writer::drawio;
+use astree::writer::layout;
+use astree::writer::png;
+use astree::writer::png::PngOptions;
+use astree::writer::svg;
+use astree::writer::template::Template;
+use astree::writer::Direction;
+use astree::writer::Format;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
 
 const LONG_ABOUT: &str = r#"
 A command line tool for drawing tree structures with ascii characters.
@@ -40,6 +60,8 @@ impl Args {
         match self.command {
             Command::Vertical(vertical_args) => vertical_args.run(),
             Command::Horizontal(horizontal_args) => horizontal_args.run(),
+            Command::Boxes(boxes_args) => boxes_args.run(),
+            Command::Mindmap(mindmap_args) => mindmap_args.run(),
         }
     }
 }
@@ -52,20 +74,284 @@ pub enum Command {
     /// Print the tree horizontally. Use 'h' for shorthand.
     #[clap(alias = "h")]
     Horizontal(HorizontalArgs),
+    /// Print the tree horizontally with boxes. Use 'b' for shorthand.
+    #[clap(alias = "b")]
+    Boxes(BoxesArgs),
+    /// Print the tree as a mind map, with the root in the middle. Use 'm' for shorthand.
+    #[clap(alias = "m")]
+    Mindmap(MindmapArgs),
+}
+
+// The options about the output, which are shared by all the subcommands
+#[derive(clap::Args, Debug)]
+pub struct OutputArgs {
+    /// The output format [default: inferred from the extension of --output, or text]
+    #[clap(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// Write to this file instead of stdout
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+
+    /// When to use colors
+    #[clap(long, value_enum, default_value = "auto")]
+    color: ColorWhen,
+
+    /// How to pick the colors of each node
+    #[clap(long, value_enum, default_value = "depth")]
+    color_by: ColorBy,
+
+    /// The indent of each level of the outline formats, and of {indent} in templates
+    #[clap(long, default_value = "  ")]
+    indent: String,
+
+    /// The template expanded before the children of each node, with the placeholders
+    /// {label}, {depth}, {index}, {path}, {is_last}, {child_count} and {indent}
+    #[clap(long, default_value = "{indent}{label}\\n")]
+    pre: Template,
+
+    /// The template expanded after the children of each node
+    #[clap(long, default_value = "")]
+    post: Template,
+
+    /// Draw the nodes of --format latex with borders resembling --style
+    #[clap(long)]
+    latex_borders: bool,
+
+    #[command(flatten)]
+    png: PngOptions,
+}
+
+impl OutputArgs {
+    // The format given by --format, or else the one matching the extension of --output
+    fn format(&self) -> Format {
+        match (self.format, &self.output) {
+            (Some(format), _) => format,
+            (None, None) => Format::Text,
+            (None, Some(path)) => Format::from_extension(path).unwrap_or_else(|| {
+                Args::command()
+                    .error(
+                        ErrorKind::InvalidValue,
+                        format!(
+                            "cannot infer the output format of '{}', please specify --format",
+                            path.display()
+                        ),
+                    )
+                    .exit()
+            }),
+        }
+    }
+
+    // Colors are only added automatically when printing to a terminal, not to a file
+    fn color_by(&self) -> Option<ColorBy> {
+        let enabled = match self.output {
+            Some(_) => self.color == ColorWhen::Always,
+            None => color::enabled(self.color),
+        };
+        enabled.then_some(self.color_by)
+    }
+
+    fn writer_options(&self, direction: Direction, style: Style) -> writer::Options {
+        writer::Options {
+            direction,
+            style,
+            latex_borders: self.latex_borders,
+            indent: self.indent.clone(),
+            pre: self.pre.clone(),
+            post: self.post.clone(),
+        }
+    }
+
+    // Render the text output of the tree, as is or as an image
+    fn render_text(&self, format: Format, text: impl Fn(Option<ColorBy>) -> String) -> Vec<u8> {
+        match format {
+            Format::Text => text(self.color_by()).into_bytes(),
+            Format::Png => png::render(&text(None), &self.png),
+            Format::Svg => svg::render(&text(None)).into_bytes(),
+            _ => unreachable!("{:?} is not drawn from the text output", format),
+        }
+    }
+
+    fn write(&self, bytes: &[u8]) {
+        let result = match &self.output {
+            Some(path) => write_atomically(path, bytes),
+            None => io::stdout().write_all(bytes),
+        };
+        if let Err(err) = result {
+            let destination = match &self.output {
+                Some(path) => path.display().to_string(),
+                None => "stdout".to_string(),
+            };
+            Args::command()
+                .error(
+                    ErrorKind::Io,
+                    format!("failed to write to {}: {}", destination, err),
+                )
+                .exit()
+        }
+    }
+}
+
+fn vertical_only(format: Format) -> ! {
+    Args::command()
+        .error(
+            ErrorKind::InvalidValue,
+            format!(
+                "the {} format is only supported by the vertical tree",
+                format.to_possible_value().unwrap().get_name()
+            ),
+        )
+        .exit()
 }
 
 #[derive(Parser, Debug)]
 pub struct HorizontalArgs {
+    #[clap(short, long, value_enum, default_value = "thin")]
+    style: Style,
+
     /// The input filename or content
     #[clap(short, long)]
     input: String,
+
+    /// The maximum width of each label
+    #[clap(short, long)]
+    width: Option<usize>,
+
+    /// Which side the root is on
+    #[clap(long, value_enum, default_value = "left-to-right")]
+    orientation: horizontal::Orientation,
+
+    #[command(flatten)]
+    output: OutputArgs,
 }
 
 impl HorizontalArgs {
     fn run(&self) {
-        // Don't support automatically adding line breaks for horizontal tree
-        let root_nodes = parse(&self.input, None);
-        horizontal::print_nodes_std(&root_nodes)
+        let root_nodes = parse(&self.input, self.width);
+        let guides = match self.orientation {
+            horizontal::Orientation::LeftToRight => GuideLines::new(self.style),
+            horizontal::Orientation::RightToLeft => GuideLines::mirrored(self.style),
+        };
+        let output = match self.output.format() {
+            format @ (Format::Text | Format::Png | Format::Svg) => {
+                self.output.render_text(format, |color_by| {
+                    horizontal::render(&root_nodes, &guides, color_by, self.orientation)
+                })
+            }
+            format @ (Format::Layout | Format::Drawio) => vertical_only(format),
+            format => writer::render(
+                format,
+                &root_nodes,
+                &self
+                    .output
+                    .writer_options(Direction::LeftToRight, self.style),
+            )
+            .into_bytes(),
+        };
+        self.output.write(&output)
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct BoxesArgs {
+    #[clap(short, long, value_enum, default_value = "thin")]
+    style: Style,
+
+    /// The input filename or content
+    #[clap(short, long)]
+    input: String,
+
+    /// The maximum width of each box
+    #[clap(short, long)]
+    width: Option<usize>,
+
+    /// The vertical spacing between boxes
+    #[clap(long, default_value_t = 0)]
+    spacing: usize,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl BoxesArgs {
+    fn run(&self) {
+        let root_nodes = parse(&self.input, self.width);
+        let style = BoxDrawings::new(self.style);
+        let output = match self.output.format() {
+            format @ (Format::Text | Format::Png | Format::Svg) => {
+                self.output.render_text(format, |color_by| {
+                    root_nodes
+                        .iter()
+                        .map(|root| {
+                            let result =
+                                horizontal_boxes::render(root, &style, self.spacing, color_by);
+                            format!("{}\n", result)
+                        })
+                        .collect()
+                })
+            }
+            format @ (Format::Layout | Format::Drawio) => vertical_only(format),
+            format => writer::render(
+                format,
+                &root_nodes,
+                &self
+                    .output
+                    .writer_options(Direction::LeftToRight, self.style),
+            )
+            .into_bytes(),
+        };
+        self.output.write(&output)
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct MindmapArgs {
+    #[clap(short, long, value_enum, default_value = "thin")]
+    style: Style,
+
+    /// The input filename or content
+    #[clap(short, long)]
+    input: String,
+
+    /// The maximum width of each box
+    #[clap(short, long)]
+    width: Option<usize>,
+
+    /// The vertical spacing between boxes
+    #[clap(long, default_value_t = 0)]
+    spacing: usize,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl MindmapArgs {
+    fn run(&self) {
+        let root_nodes = parse(&self.input, self.width);
+        let style = BoxDrawings::new(self.style);
+        let output = match self.output.format() {
+            format @ (Format::Text | Format::Png | Format::Svg) => {
+                self.output.render_text(format, |color_by| {
+                    root_nodes
+                        .iter()
+                        .map(|root| {
+                            let result = mindmap::render(root, &style, self.spacing, color_by);
+                            format!("{}\n", result)
+                        })
+                        .collect()
+                })
+            }
+            format @ (Format::Layout | Format::Drawio) => vertical_only(format),
+            format => writer::render(
+                format,
+                &root_nodes,
+                &self
+                    .output
+                    .writer_options(Direction::LeftToRight, self.style),
+            )
+            .into_bytes(),
+        };
+        self.output.write(&output)
     }
 }
 
@@ -85,16 +371,210 @@ pub struct VerticalArgs {
     /// The horizontal spacing between boxes
     #[clap(long, default_value_t = 2)]
     spacing: usize,
+
+    /// The rows between the levels, for the connectors. A single child is one row
+    /// closer, as it needs no horizontal bar.
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    level_spacing: u64,
+
+    /// Start all the nodes of the same depth on the same row, even below boxes of
+    /// different heights
+    #[clap(long)]
+    uniform_levels: bool,
+
+    /// Whether the root is at the top or at the bottom
+    #[clap(long, value_enum, default_value = "top-down")]
+    orientation: vertical::Orientation,
+
+    /// Pack the subtrees together where they do not collide, for narrower trees
+    #[clap(long)]
+    compact: bool,
+
+    /// Stack the leaves among the children in a column, as in org charts, while the
+    /// other children stay side by side
+    #[clap(long)]
+    stack_leaves: bool,
+
+    /// Tighten the layout until the tree fits in the terminal, by reducing the spacing,
+    /// wrapping the labels, stacking the leaves, or else printing it horizontally
+    #[clap(long)]
+    fit: bool,
+
+    /// How to draw several trees, e.g. several headings of the top level
+    #[clap(long, value_enum, default_value = "stacked")]
+    forest: vertical::Forest,
+
+    /// The horizontal spacing between the trees with --forest side-by-side
+    #[clap(long, default_value_t = 4)]
+    forest_gap: usize,
+
+    /// The label of the synthetic root with --forest root
+    #[clap(long, default_value = ".")]
+    root_label: String,
+
+    /// Cut the tree into panels when it is too wide, with the widest subtrees on their
+    /// own panels, referred to as [see <LABEL>] in their parent panels
+    #[clap(long, conflicts_with = "fit")]
+    split: bool,
+
+    /// The width to fit the tree in with --fit, or the width of the panels with --split
+    /// [default: the COLUMNS environment variable, or 80]
+    #[clap(long)]
+    max_width: Option<usize>,
+
+    #[command(flatten)]
+    output: OutputArgs,
 }
 
 impl VerticalArgs {
     fn run(self) {
-        let root_nodes = parse(&self.input, self.width);
-        for root in root_nodes {
-            let result = render(&root, &BoxDrawings::new(self.style), self.spacing);
-            println!("{}", result);
+        let mut root_nodes = parse(&self.input, self.width);
+        let style = BoxDrawings::new(self.style);
+        let mut options = vertical::LayoutOptions {
+            horizontal_spacing: self.spacing,
+            compact: self.compact,
+            stack_leaves: self.stack_leaves,
+            vertical_spacing: self.level_spacing as usize,
+            uniform_levels: self.uniform_levels,
+        };
+
+        // Only the trees drawn as text have to fit in the width
+        let format = self.output.format();
+        let drawn = matches!(
+            format,
+            Format::Text | Format::Png | Format::Svg | Format::Layout | Format::Drawio
+        );
+        if drawn && self.forest == vertical::Forest::Root && root_nodes.len() > 1 {
+            root_nodes = vec![TreeNode {
+                label: self.root_label.clone(),
+                children: root_nodes,
+            }];
+        }
+        // The panels of a split tree are titled, so they are always stacked
+        let side_by_side = self.forest == vertical::Forest::SideBySide && !self.split;
+
+        let max_width = self.max_width.unwrap_or_else(fit::terminal_width);
+        let mut horizontally = false;
+        let mut titles = vec![None; root_nodes.len()];
+        if self.fit && matches!(format, Format::Text | Format::Png | Format::Svg) {
+            let fitted = fit::fit(
+                root_nodes,
+                &options,
+                max_width,
+                side_by_side.then_some(self.forest_gap),
+            );
+            eprintln!("{}", fitted.summary());
+            horizontally = fitted.strategy == fit::Strategy::Horizontal;
+            root_nodes = fitted.roots;
+            options = fitted.options;
+        }
+
+        // The panels are drawn one after another, as if they were separate trees
+        if self.split && drawn {
+            let panels: Vec<panels::Panel> = root_nodes
+                .into_iter()
+                .flat_map(|root| panels::split(root, &options, max_width))
+                .collect();
+            for panel in &panels {
+                let width = panel.width(&options);
+                if width > max_width {
+                    eprintln!(
+                        "The panel {} is {} columns wide, as it cannot be split to fit in {} columns",
+                        panel.title.as_deref().unwrap_or("of the root"),
+                        width,
+                        max_width
+                    );
+                }
+            }
+            (titles, root_nodes) = panels
+                .into_iter()
+                .map(|panel| (panel.title, panel.root))
+                .unzip();
         }
+
+        let layouts = || -> Vec<vertical::Layout> {
+            if side_by_side {
+                let layout = vertical::layout_forest(
+                    &root_nodes,
+                    &options,
+                    self.orientation,
+                    self.forest_gap,
+                );
+                return vec![layout];
+            }
+            root_nodes
+                .iter()
+                .map(|root| vertical::layout(root, &options, self.orientation))
+                .collect()
+        };
+
+        let output = match format {
+            format @ (Format::Text | Format::Png | Format::Svg) if horizontally => {
+                let guides = GuideLines::new(self.style);
+                self.output.render_text(format, |color_by| {
+                    horizontal::render(
+                        &root_nodes,
+                        &guides,
+                        color_by,
+                        horizontal::Orientation::LeftToRight,
+                    )
+                })
+            }
+            format @ (Format::Text | Format::Png | Format::Svg) if side_by_side => {
+                self.output.render_text(format, |color_by| {
+                    let result = vertical::render_forest(
+                        &root_nodes,
+                        &style,
+                        &options,
+                        color_by,
+                        self.orientation,
+                        self.forest_gap,
+                    );
+                    format!("{}\n", result)
+                })
+            }
+            format @ (Format::Text | Format::Png | Format::Svg) => {
+                self.output.render_text(format, |color_by| {
+                    root_nodes
+                        .iter()
+                        .zip(&titles)
+                        .map(|(root, title)| {
+                            let result = render(root, &style, &options, color_by, self.orientation);
+                            match title {
+                                Some(title) => format!("\n{}\n{}\n", title, result),
+                                None => format!("{}\n", result),
+                            }
+                        })
+                        .collect()
+                })
+            }
+            Format::Layout => layout::render(&layouts()).into_bytes(),
+            Format::Drawio => drawio::render(&layouts(), self.style).into_bytes(),
+            format => writer::render(
+                format,
+                &root_nodes,
+                &self
+                    .output
+                    .writer_options(Direction::TopToBottom, self.style),
+            )
+            .into_bytes(),
+        };
+        self.output.write(&output)
+    }
+}
+
+// Write to a temporary file next to the destination and then rename it, so that the
+// destination is never left half written, e.g. when a build is interrupted.
+fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}.tmp", process::id()));
+    let temporary = PathBuf::from(temporary);
+
+    let result = fs::write(&temporary, bytes).and_then(|_| fs::rename(&temporary, path));
+    if result.is_err() {
+        _ = fs::remove_file(&temporary);
     }
+    result
 }
 
 fn main() {
@@ -16,7 +16,9 @@ pub fn parse(filename_or_content: &String, width: Option<usize>) -> Vec<TreeNode
     parse_markdown(content, width)
 }
 
-fn wrap_line(line: &str, width: Option<usize>) -> String {
+// Break the line into lines shorter than the width, between words. A word longer than
+// the width stays on a line of its own.
+pub fn wrap_line(line: &str, width: Option<usize>) -> String {
     match width {
         Some(w) => {
             let words = line.split(' ').collect_vec();
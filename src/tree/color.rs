@@ -0,0 +1,186 @@
+use std::env;
+use std::io;
+use std::io::IsTerminal;
+
+// When to use colors in the output
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorWhen {
+    /// Only when printing to a terminal, and NO_COLOR is not set
+    Auto,
+    Always,
+    Never,
+}
+
+// How to pick the colors of each node
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorBy {
+    /// A different color for each level of the tree
+    Depth,
+    /// One color for the leaves, and another one for the other nodes
+    Leaf,
+    /// The same colors for all the nodes
+    Fixed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    // The ANSI escape code to switch to this color
+    fn escape_code(&self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+const DEPTH_PALETTE: [Color; 6] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+    Color::Red,
+];
+
+// The colors to draw a node with, where None is the default color of the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeColors {
+    pub border: Option<Color>,
+    pub label: Option<Color>,
+    // The lines connecting the node to its children
+    pub connector: Option<Color>,
+}
+
+impl NodeColors {
+    pub const NONE: NodeColors = NodeColors {
+        border: None,
+        label: None,
+        connector: None,
+    };
+
+    pub fn new(color_by: Option<ColorBy>, depth: usize, is_leaf: bool) -> NodeColors {
+        match color_by {
+            None => NodeColors::NONE,
+            Some(ColorBy::Depth) => {
+                let color = Some(DEPTH_PALETTE[depth % DEPTH_PALETTE.len()]);
+                NodeColors {
+                    border: color,
+                    label: color,
+                    connector: color,
+                }
+            }
+            Some(ColorBy::Leaf) => {
+                let color = if is_leaf { Color::Green } else { Color::Blue };
+                NodeColors {
+                    border: Some(color),
+                    label: Some(color),
+                    connector: Some(Color::Blue),
+                }
+            }
+            Some(ColorBy::Fixed) => NodeColors {
+                border: Some(Color::Cyan),
+                label: None,
+                connector: Some(Color::Gray),
+            },
+        }
+    }
+}
+
+// Whether to use colors when printing to stdout. See https://no-color.org/ for NO_COLOR.
+pub fn enabled(when: ColorWhen) -> bool {
+    match when {
+        ColorWhen::Always => true,
+        ColorWhen::Never => false,
+        ColorWhen::Auto => {
+            let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            !no_color && io::stdout().is_terminal()
+        }
+    }
+}
+
+pub fn paint(text: &str, color: Option<Color>) -> String {
+    match color {
+        Some(color) if !text.is_empty() => format!("{}{}{}", color.escape_code(), text, RESET),
+        _ => text.to_string(),
+    }
+}
+
+// Join a row of colored characters, with a single escape code for each run of
+// characters with the same color.
+pub fn paint_row(row: &[(char, Option<Color>)]) -> String {
+    let mut output = String::new();
+    let mut run = String::new();
+    let mut run_color: Option<Color> = None;
+    for &(ch, color) in row {
+        // Spaces are invisible anyway, so they can join any run
+        if color != run_color && ch != ' ' {
+            output.push_str(&paint(&run, run_color));
+            run.clear();
+            run_color = color;
+        }
+        run.push(ch);
+    }
+    output.push_str(&paint(&run, run_color));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("Root", None), "Root");
+        assert_eq!(paint("Root", Some(Color::Red)), "\x1b[31mRoot\x1b[0m");
+    }
+
+    #[test]
+    fn test_paint_row() {
+        let row = vec![
+            ('│', Some(Color::Cyan)),
+            (' ', None),
+            ('a', None),
+            (' ', Some(Color::Cyan)),
+            ('│', Some(Color::Cyan)),
+            (' ', None),
+        ];
+        assert_eq!(paint_row(&row), "\x1b[36m│ \x1b[0ma \x1b[36m│ \x1b[0m");
+
+        let row = vec![('a', None), ('b', None)];
+        assert_eq!(paint_row(&row), "ab");
+    }
+
+    #[test]
+    fn test_node_colors() {
+        assert_eq!(NodeColors::new(None, 0, false), NodeColors::NONE);
+        assert_eq!(
+            NodeColors::new(Some(ColorBy::Depth), 7, true).border,
+            Some(Color::Green)
+        );
+        assert_eq!(
+            NodeColors::new(Some(ColorBy::Leaf), 1, true).label,
+            Some(Color::Green)
+        );
+        assert_eq!(
+            NodeColors::new(Some(ColorBy::Leaf), 1, false).label,
+            Some(Color::Blue)
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use crate::tree::tree_node::TreeNode;
+use crate::tree::vertical::{DrawableTreeNode, LayoutOptions};
+use std::env;
+
+// The width used when the terminal does not tell its own
+static DEFAULT_WIDTH: usize = 80;
+
+// Labels are not wrapped narrower than this, as the boxes get too tall to read
+static MIN_LABEL_WIDTH: usize = 10;
+
+// The width of the terminal, from the COLUMNS environment variable, which shells only
+// set for the child processes when it is exported
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+// How the vertical tree was tightened to fit in the width, from the least to the most
+// intrusive. Each strategy also keeps the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    AsIs,
+    ReduceSpacing,
+    WrapLabels,
+    StackLeaves,
+    // Nothing was enough, so the tree is printed horizontally instead
+    Horizontal,
+}
+
+#[derive(Debug)]
+pub struct Fit {
+    pub strategy: Strategy,
+    pub max_width: usize,
+
+    // The trees to render, with the labels wrapped if needed
+    pub roots: Vec<TreeNode>,
+    pub options: LayoutOptions,
+
+    // The width the labels are wrapped at, as with --width
+    pub label_width: Option<usize>,
+}
+
+impl Fit {
+    // A summary of the chosen strategy, with the flags giving the same tree
+    pub fn summary(&self) -> String {
+        let mut flags = vec![format!("--spacing {}", self.options.horizontal_spacing)];
+        if let Some(label_width) = self.label_width {
+            flags.push(format!("--width {}", label_width));
+        }
+        if self.strategy == Strategy::StackLeaves {
+            flags.push("--stack-leaves".to_string());
+        }
+        let flags = flags.join(" ");
+
+        match self.strategy {
+            Strategy::AsIs => format!("The tree fits in {} columns as is", self.max_width),
+            Strategy::ReduceSpacing => format!(
+                "Reduced the spacing to fit in {} columns, i.e. {}",
+                self.max_width, flags
+            ),
+            Strategy::WrapLabels => format!(
+                "Wrapped the labels to fit in {} columns, i.e. {}",
+                self.max_width, flags
+            ),
+            Strategy::StackLeaves => format!(
+                "Stacked the leaves to fit in {} columns, i.e. {}",
+                self.max_width, flags
+            ),
+            Strategy::Horizontal => format!(
+                "Switched to the horizontal tree, as the vertical tree does not fit in {} columns",
+                self.max_width
+            ),
+        }
+    }
+}
+
+// Progressively tighten the vertical tree until it fits in the width: reduce the
+// spacing, then wrap the labels narrower, then stack the leaves, and finally give up
+// and fall back to the horizontal tree. The gap is the one between the trees when they
+// are printed side by side, or None when they are printed one after another.
+pub fn fit(
+    roots: Vec<TreeNode>,
+    options: &LayoutOptions,
+    max_width: usize,
+    gap: Option<usize>,
+) -> Fit {
+    let result = |strategy, roots, options, label_width| Fit {
+        strategy,
+        max_width,
+        roots,
+        options,
+        label_width,
+    };
+    let fits =
+        |roots: &[TreeNode], options: &LayoutOptions| width(roots, options, gap) <= max_width;
+
+    if fits(&roots, options) {
+        return result(Strategy::AsIs, roots, *options, None);
+    }
+
+    for horizontal_spacing in (0..options.horizontal_spacing).rev() {
+        let spaced = LayoutOptions {
+            horizontal_spacing,
+            ..*options
+        };
+        if fits(&roots, &spaced) {
+            return result(Strategy::ReduceSpacing, roots, spaced, None);
+        }
+    }
+
+    let tight = LayoutOptions {
+        horizontal_spacing: 0,
+        ..*options
+    };
+    for label_width in label_widths(&roots) {
+        let wrapped = wrap(&roots, label_width);
+        if fits(&wrapped, &tight) {
+            return result(Strategy::WrapLabels, wrapped, tight, Some(label_width));
+        }
+    }
+
+    if !options.stack_leaves {
+        let stacked = LayoutOptions {
+            stack_leaves: true,
+            ..tight
+        };
+        if fits(&roots, &stacked) {
+            return result(Strategy::StackLeaves, roots, stacked, None);
+        }
+        for label_width in label_widths(&roots) {
+            let wrapped = wrap(&roots, label_width);
+            if fits(&wrapped, &stacked) {
+                return result(Strategy::StackLeaves, wrapped, stacked, Some(label_width));
+            }
+        }
+    }
+
+    result(Strategy::Horizontal, roots, *options, None)
+}
+
+// The width of the widest tree when they are printed one after another, or of all the
+// trees and the gaps in between when they are printed side by side
+fn width(roots: &[TreeNode], options: &LayoutOptions, gap: Option<usize>) -> usize {
+    let widths = roots
+        .iter()
+        .map(|root| DrawableTreeNode::with_options(root, options).overall_width);
+    match gap {
+        Some(gap) => widths.sum::<usize>() + gap * roots.len().saturating_sub(1),
+        None => widths.max().unwrap_or(0),
+    }
+}
+
+fn wrap(roots: &[TreeNode], label_width: usize) -> Vec<TreeNode> {
+    roots.iter().map(|root| root.wrapped(label_width)).collect()
+}
+
+// The widths to wrap the labels at, from the widest to the narrowest. Lines are wrapped
+// shorter than the width, so the first one already breaks the longest lines.
+fn label_widths(roots: &[TreeNode]) -> impl Iterator<Item = usize> {
+    fn longest_line(node: &TreeNode) -> usize {
+        let lines = node.label_lines().iter().map(|line| line.len()).max();
+        let children = node.children.iter().map(longest_line).max();
+        lines.max(children).unwrap_or(0)
+    }
+
+    let longest = roots.iter().map(longest_line).max().unwrap_or(0);
+    (MIN_LABEL_WIDTH..=longest).rev()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team() -> TreeNode {
+        TreeNode::new(
+            "Platform Team",
+            vec![
+                TreeNode::from_label("Infrastructure Engineer"),
+                TreeNode::from_label("Site Reliability Engineer"),
+                TreeNode::from_label("Developer Experience"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_as_is() {
+        let fit = fit(vec![team()], &LayoutOptions::default(), 100, None);
+        assert_eq!(fit.strategy, Strategy::AsIs);
+        assert_eq!(fit.options.horizontal_spacing, 2);
+        assert_eq!(fit.summary(), "The tree fits in 100 columns as is");
+    }
+
+    #[test]
+    fn test_reduce_spacing() {
+        // 27 + 29 + 24 columns for the boxes, and 2 for each gap
+        assert_eq!(width(&[team()], &LayoutOptions::default(), None), 84);
+
+        let fit = fit(vec![team()], &LayoutOptions::default(), 82, None);
+        assert_eq!(fit.strategy, Strategy::ReduceSpacing);
+        assert_eq!(fit.options.horizontal_spacing, 1);
+        assert_eq!(
+            fit.summary(),
+            "Reduced the spacing to fit in 82 columns, i.e. --spacing 1"
+        );
+    }
+
+    #[test]
+    fn test_wrap_labels() {
+        let fit = fit(vec![team()], &LayoutOptions::default(), 60, None);
+        assert_eq!(fit.strategy, Strategy::WrapLabels);
+        assert_eq!(fit.options.horizontal_spacing, 0);
+        assert!(width(&fit.roots, &fit.options, None) <= 60);
+        assert_eq!(
+            fit.roots[0].children[1].label_lines(),
+            vec!["Site Reliability", "Engineer"]
+        );
+        assert_eq!(
+            fit.summary(),
+            "Wrapped the labels to fit in 60 columns, i.e. --spacing 0 --width 20"
+        );
+    }
+
+    #[test]
+    fn test_stack_leaves() {
+        let fit = fit(vec![team()], &LayoutOptions::default(), 40, None);
+        assert_eq!(fit.strategy, Strategy::StackLeaves);
+        assert!(fit.options.stack_leaves);
+        assert_eq!(fit.label_width, None);
+        assert_eq!(
+            fit.summary(),
+            "Stacked the leaves to fit in 40 columns, i.e. --spacing 0 --stack-leaves"
+        );
+    }
+
+    #[test]
+    fn test_horizontal() {
+        let fit = fit(vec![team()], &LayoutOptions::default(), 10, None);
+        assert_eq!(fit.strategy, Strategy::Horizontal);
+        assert_eq!(fit.roots, vec![team()]);
+        assert_eq!(fit.options.horizontal_spacing, 2);
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        // Each tree fits on its own, but not next to each other
+        let roots = || vec![team(), TreeNode::from_label("Another Team")];
+        assert_eq!(width(&roots(), &LayoutOptions::default(), None), 84);
+        assert_eq!(width(&roots(), &LayoutOptions::default(), Some(4)), 104);
+
+        let stacked = fit(roots(), &LayoutOptions::default(), 90, None);
+        assert_eq!(stacked.strategy, Strategy::AsIs);
+        let fit = fit(roots(), &LayoutOptions::default(), 90, Some(4));
+        assert_eq!(fit.strategy, Strategy::WrapLabels);
+        assert!(width(&fit.roots, &fit.options, Some(4)) <= 90);
+    }
+}
@@ -1,39 +1,145 @@
+use crate::tree::color::{paint, ColorBy, NodeColors};
+use crate::tree::style::GuideLines;
 use crate::tree::tree_node::TreeNode;
-use std::io;
 use std::io::Write;
 use std::str;
 
-// This is the public interface to be called outside
-pub fn print_nodes_std(children: &Vec<TreeNode>) {
-    print_nodes(children, &mut io::stdout())
+// Render the forest with the guides facing the orientation, i.e. `GuideLines::new` for
+// left to right and `GuideLines::mirrored` for right to left
+pub fn render(
+    roots: &[TreeNode],
+    guides: &GuideLines,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+) -> String {
+    let mut output: Vec<u8> = Vec::new();
+    print_nodes(roots, guides, color_by, orientation, &mut output);
+    String::from_utf8(output).expect("Invalid UTF-8")
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The root on the left, and the children indented to the right
+    LeftToRight,
+    /// The root on the right, and the children indented to the left, e.g. for
+    /// right-to-left languages
+    RightToLeft,
+}
+
+// A line of the tree, i.e. the guides from the root outwards, and the label
+struct Line {
+    guides: Vec<String>,
+    label: String,
+
+    // The number of characters, without the colors
+    width: usize,
 }
 
 // This function allow us to write unit tests easily
-fn print_nodes(roots: &Vec<TreeNode>, output: &mut impl Write) {
-    fn print_nodes_internal(children: &Vec<TreeNode>, prefix: &str, output: &mut impl Write) {
+fn print_nodes(
+    roots: &[TreeNode],
+    guides: &GuideLines,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+    output: &mut impl Write,
+) {
+    let lines = lines(roots, guides, color_by);
+    let width = lines.iter().map(|line| line.width).max().unwrap_or(0);
+    for line in lines {
+        let line = match orientation {
+            Orientation::LeftToRight => format!("{}{}", line.guides.concat(), line.label),
+            //             Root
+            //       Child 1 ─┤
+            // Grandchild ─┘  │
+            //       Child 2 ─┘
+            Orientation::RightToLeft => {
+                let guides: Vec<&str> = line.guides.iter().rev().map(|x| x.as_str()).collect();
+                let padding = " ".repeat(width - line.width);
+                format!("{}{}{}", padding, line.label, guides.concat())
+            }
+        };
+        _ = output.write_all(format!("{}\n", line).as_bytes());
+    }
+}
+
+fn lines(roots: &[TreeNode], guides: &GuideLines, color_by: Option<ColorBy>) -> Vec<Line> {
+    fn push_lines(
+        children: &[TreeNode],
+        prefix: &[String],
+        prefix_width: usize,
+        guides: &GuideLines,
+        color_by: Option<ColorBy>,
+        depth: usize,
+        lines: &mut Vec<Line>,
+    ) {
+        // The guide lines are the connectors of the parent, which is either at the
+        // previous depth, or the artificial dot without any color.
+        let connector = match depth.checked_sub(1) {
+            Some(parent_depth) => NodeColors::new(color_by, parent_depth, false).connector,
+            None => None,
+        };
+
         for (idx, child) in children.iter().enumerate() {
-            if idx < children.len() - 1 {
-                _ = output.write_all(format!("{}├─ {}\n", prefix, child.label).as_bytes());
-                print_nodes_internal(&child.children, &format!("{}{}", prefix, "│  "), output);
+            let is_last = idx == children.len() - 1;
+            let (branch, continuation) = if is_last {
+                (
+                    guides.last_branch,
+                    " ".repeat(guides.continuation.chars().count()),
+                )
             } else {
-                _ = output.write_all(format!("{}└─ {}\n", prefix, child.label).as_bytes());
-                print_nodes_internal(&child.children, &format!("{}{}", prefix, "   "), output);
+                (guides.branch, paint(guides.continuation, connector))
+            };
+            let mut prefix_continuation = prefix.to_vec();
+            prefix_continuation.push(continuation);
+            let label_color = NodeColors::new(color_by, depth, child.children.is_empty()).label;
+
+            // The following lines of a multi-line label are aligned with the first one
+            for (line_idx, line) in child.label_lines().iter().enumerate() {
+                let guides = if line_idx == 0 {
+                    let mut guides = prefix.to_vec();
+                    guides.push(paint(branch, connector));
+                    guides
+                } else {
+                    prefix_continuation.clone()
+                };
+                lines.push(Line {
+                    guides,
+                    label: paint(line, label_color),
+                    width: prefix_width + branch.chars().count() + line.chars().count(),
+                });
             }
+            push_lines(
+                &child.children,
+                &prefix_continuation,
+                prefix_width + branch.chars().count(),
+                guides,
+                color_by,
+                depth + 1,
+                lines,
+            );
         }
     }
 
-    let mut write_line = |buf: &str| {
-        _ = output.write_all(format!("{}\n", buf).as_bytes());
+    let mut lines = vec![];
+    let root_line = |label: &str, color| Line {
+        guides: vec![],
+        label: paint(label, color),
+        width: label.chars().count(),
     };
 
     if roots.len() > 1 {
         // if there are more than one root nodes, let's add an artificial dot as the global root
-        write_line(".");
-        print_nodes_internal(&roots, "", output)
+        lines.push(root_line(".", None));
+        push_lines(roots, &[], 0, guides, color_by, 0, &mut lines)
     } else if roots.len() == 1 {
-        write_line(&roots[0].label);
-        print_nodes_internal(&roots[0].children, "", output)
+        let root = &roots[0];
+        let label_color = NodeColors::new(color_by, 0, root.children.is_empty()).label;
+        for line in root.label_lines() {
+            lines.push(root_line(&line, label_color));
+        }
+        push_lines(&root.children, &[], 0, guides, color_by, 1, &mut lines)
     }
+    lines
 }
 
 #[cfg(test)]
@@ -45,7 +151,13 @@ mod layout_tests {
     fn test_print_single_root() {
         let mut output: Vec<u8> = Vec::new();
 
-        print_nodes(&vec![TreeNode::new("Root", vec![])], &mut output);
+        print_nodes(
+            &vec![TreeNode::new("Root", vec![])],
+            &GuideLines::THIN,
+            None,
+            Orientation::LeftToRight,
+            &mut output,
+        );
 
         assert_canonical_eq(
             r#"
@@ -67,6 +179,9 @@ mod layout_tests {
                     TreeNode::new("Child 2", vec![]),
                 ],
             )],
+            &GuideLines::THIN,
+            None,
+            Orientation::LeftToRight,
             &mut output,
         );
 
@@ -89,6 +204,9 @@ mod layout_tests {
                 TreeNode::new("Root 1", vec![]),
                 TreeNode::new("Root 2", vec![]),
             ],
+            &GuideLines::THIN,
+            None,
+            Orientation::LeftToRight,
             &mut output,
         );
 
@@ -123,6 +241,9 @@ mod layout_tests {
                     ],
                 ),
             ],
+            &GuideLines::THIN,
+            None,
+            Orientation::LeftToRight,
             &mut output,
         );
 
@@ -139,4 +260,134 @@ mod layout_tests {
             str::from_utf8(&output).expect("Invalid UTF-8"),
         )
     }
+
+    #[test]
+    fn test_print_multi_line_labels() {
+        let mut output: Vec<u8> = Vec::new();
+
+        print_nodes(
+            &vec![TreeNode::new(
+                "Root\\nNode",
+                vec![
+                    TreeNode::new(
+                        "Child 1\\nQuota: 100",
+                        vec![TreeNode::new("Grandchild\\nQuota: 10", vec![])],
+                    ),
+                    TreeNode::new("Child 2\\nQuota: 200", vec![]),
+                ],
+            )],
+            &GuideLines::THIN,
+            None,
+            Orientation::LeftToRight,
+            &mut output,
+        );
+
+        assert_canonical_eq(
+            r#"
+            Root
+            Node
+            ├─ Child 1
+            │  Quota: 100
+            │  └─ Grandchild
+            │     Quota: 10
+            └─ Child 2
+               Quota: 200
+            "#,
+            str::from_utf8(&output).expect("Invalid UTF-8"),
+        )
+    }
+
+    #[test]
+    fn test_print_right_to_left() {
+        let mut output: Vec<u8> = Vec::new();
+
+        print_nodes(
+            &vec![TreeNode::new(
+                "Root",
+                vec![
+                    TreeNode::new(
+                        "Child 1\\nQuota: 100",
+                        vec![TreeNode::new("Grandchild", vec![])],
+                    ),
+                    TreeNode::new("Child 2", vec![]),
+                ],
+            )],
+            &GuideLines::THIN_MIRRORED,
+            None,
+            Orientation::RightToLeft,
+            &mut output,
+        );
+
+        assert_eq!(
+            str::from_utf8(&output).expect("Invalid UTF-8"),
+            [
+                "            Root",
+                "      Child 1 ─┤",
+                "   Quota: 100  │",
+                "Grandchild ─┘  │",
+                "      Child 2 ─┘",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_print_ascii() {
+        let mut output: Vec<u8> = Vec::new();
+
+        print_nodes(
+            &vec![TreeNode::new(
+                "Root",
+                vec![
+                    TreeNode::new("Child 1", vec![TreeNode::new("Grandchild", vec![])]),
+                    TreeNode::new("Child 2", vec![]),
+                ],
+            )],
+            &GuideLines::ASCII,
+            None,
+            Orientation::LeftToRight,
+            &mut output,
+        );
+
+        assert_canonical_eq(
+            r#"
+            Root
+            |- Child 1
+            |  `- Grandchild
+            `- Child 2
+            "#,
+            str::from_utf8(&output).expect("Invalid UTF-8"),
+        )
+    }
+
+    #[test]
+    fn test_print_colored_by_depth() {
+        let mut output: Vec<u8> = Vec::new();
+
+        print_nodes(
+            &vec![TreeNode::new(
+                "Root",
+                vec![TreeNode::new(
+                    "Child",
+                    vec![TreeNode::new("Grandchild", vec![])],
+                )],
+            )],
+            &GuideLines::THIN,
+            Some(ColorBy::Depth),
+            Orientation::LeftToRight,
+            &mut output,
+        );
+
+        assert_eq!(
+            str::from_utf8(&output).expect("Invalid UTF-8"),
+            [
+                "\x1b[36mRoot\x1b[0m",
+                "\x1b[36m└─ \x1b[0m\x1b[32mChild\x1b[0m",
+                "   \x1b[32m└─ \x1b[0m\x1b[33mGrandchild\x1b[0m",
+                "",
+            ]
+            .join("\n")
+        )
+    }
 }
@@ -0,0 +1,484 @@
+use crate::tree::color::{paint_row, Color, ColorBy, NodeColors};
+use crate::tree::horizontal::Orientation;
+use crate::tree::style::BoxDrawings;
+use crate::tree::tree_node::TreeNode;
+use crate::tree::vertical::Point2D;
+use std::cmp::max;
+
+pub fn render(
+    tree_node: &TreeNode,
+    style: &BoxDrawings,
+    vertical_spacing: usize,
+    color_by: Option<ColorBy>,
+) -> String {
+    let boxed = BoxedTreeNode::new(tree_node, vertical_spacing);
+    boxed.render_colored(style, vertical_spacing, color_by)
+}
+
+// The columns between a node and its children, i.e. a horizontal bar, the vertical
+// bar connecting all the children, and another horizontal bar.
+// ┌──────┐   ┌─────────┐
+// │ Root ├─┬─┤ Child 1 │
+// └──────┘ │ └─────────┘
+//         ↑↑↑
+static HORIZONTAL_LAYER_BUFFER: usize = 3;
+
+// A character on the canvas, and the color to draw it with
+pub type Cell = (char, Option<Color>);
+
+// The settings that are the same for all the nodes of the tree being drawn
+struct Pen<'a> {
+    style: &'a BoxDrawings,
+    vertical_spacing: usize,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+}
+
+// The same boxes as `DrawableTreeNode`, but with the root on the left and the children
+// stacked on the right, e.g.
+// ┌──────┐   ┌─────────┐
+// │ Root ├─┬─┤ Child 1 │
+// └──────┘ │ └─────────┘
+//          │ ┌─────────┐
+//          └─┤ Child 2 │
+//            └─────────┘
+#[derive(Debug)]
+pub struct BoxedTreeNode {
+    // Vertical center of the current node
+    pub center_y: usize,
+
+    // Size of the node
+    pub width: usize,
+    height: usize,
+
+    // Size of the node with all its children (if any)
+    pub overall_width: usize,
+    pub overall_height: usize,
+
+    labels: Vec<String>,
+
+    // The additional padding above the children, when the current node is taller
+    // than the bar connecting the children
+    children_top_offset: usize,
+
+    children: Vec<BoxedTreeNode>,
+}
+
+impl BoxedTreeNode {
+    pub fn new(node: &TreeNode, vertical_spacing: usize) -> Self {
+        BoxedTreeNode::with_children(node, &node.children, vertical_spacing)
+    }
+
+    // The node with only some of its children, e.g. one side of a mind map
+    pub fn with_children(node: &TreeNode, children: &[TreeNode], vertical_spacing: usize) -> Self {
+        let labels: Vec<String> = node.label_lines();
+
+        // A space on both side, and two vertical bars
+        let width = labels.iter().map(|x| x.len()).max().unwrap() + 4;
+        // One horizontal bar at the top, one at the bottom
+        let height = labels.len() + 2;
+
+        let children: Vec<BoxedTreeNode> = children
+            .iter()
+            .map(|x| BoxedTreeNode::new(x, vertical_spacing))
+            .collect();
+
+        // When height is even, the center is closer to the top
+        let center_of_current_box = (height - 1) / 2;
+
+        let (center_y, children_top_offset, overall_width, overall_height) =
+            match (children.first(), children.last()) {
+                (Some(first), Some(last)) => {
+                    // We put all the children below each other, with some space in between
+                    let children_height = children
+                        .iter()
+                        .map(|child| child.overall_height)
+                        .sum::<usize>()
+                        + (children.len() - 1) * vertical_spacing;
+
+                    // Align the current node to the middle of the bar connecting the children
+                    let last_center = children_height - last.overall_height + last.center_y;
+                    let center_of_children = (first.center_y + last_center) / 2;
+
+                    let overall_center = max(center_of_current_box, center_of_children);
+                    let children_top_offset = overall_center - center_of_children;
+                    let overall_height = max(
+                        overall_center - center_of_current_box + height,
+                        children_top_offset + children_height,
+                    );
+                    let children_width = children
+                        .iter()
+                        .map(|child| child.overall_width)
+                        .max()
+                        .unwrap_or(0);
+
+                    (
+                        overall_center,
+                        children_top_offset,
+                        width + HORIZONTAL_LAYER_BUFFER + children_width,
+                        overall_height,
+                    )
+                }
+                _ => (center_of_current_box, 0, width, height),
+            };
+
+        BoxedTreeNode {
+            center_y,
+            width,
+            height,
+            overall_width,
+            overall_height,
+            labels,
+            children_top_offset,
+            children,
+        }
+    }
+
+    // Render the tree with ANSI colors picked by `color_by`, or without colors if None
+    pub fn render_colored(
+        &self,
+        style: &BoxDrawings,
+        vertical_spacing: usize,
+        color_by: Option<ColorBy>,
+    ) -> String {
+        self.render_oriented(style, vertical_spacing, color_by, Orientation::LeftToRight)
+    }
+
+    pub fn render_oriented(
+        &self,
+        style: &BoxDrawings,
+        vertical_spacing: usize,
+        color_by: Option<ColorBy>,
+        orientation: Orientation,
+    ) -> String {
+        self.draw(style, vertical_spacing, color_by, orientation)
+            .iter()
+            .map(|row| paint_row(row))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Draw the tree on a canvas of `overall_width` by `overall_height` cells. Right to
+    // left, the tree is drawn with the mirrored box drawings and then mirrored column
+    // by column.
+    pub fn draw(
+        &self,
+        style: &BoxDrawings,
+        vertical_spacing: usize,
+        color_by: Option<ColorBy>,
+        orientation: Orientation,
+    ) -> Vec<Vec<Cell>> {
+        let mirrored;
+        let style = match orientation {
+            Orientation::LeftToRight => style,
+            Orientation::RightToLeft => {
+                mirrored = style.mirrored();
+                &mirrored
+            }
+        };
+        let pen = Pen {
+            style,
+            vertical_spacing,
+            color_by,
+            orientation,
+        };
+
+        let mut canvas: Vec<Vec<Cell>> =
+            vec![vec![(' ', None); self.overall_width]; self.overall_height];
+
+        self.render_internal(&mut canvas, &Point2D { x: 0, y: 0 }, &pen, 0);
+
+        if orientation == Orientation::RightToLeft {
+            for row in &mut canvas {
+                row.reverse();
+            }
+        }
+        canvas
+    }
+
+    fn render_internal(
+        &self,
+        buffer: &mut [Vec<Cell>],
+        origin: &Point2D<usize>,
+        pen: &Pen,
+        depth: usize,
+    ) {
+        let style = pen.style;
+        let colors = NodeColors::new(pen.color_by, depth, self.children.is_empty());
+        let border = colors.border;
+
+        let left = origin.x;
+        let right = left + self.width;
+        let top = origin.y + self.center_y - (self.height - 1) / 2;
+        let bottom = top + self.height;
+
+        for y in [top, bottom - 1] {
+            for cell in &mut buffer[y][left + 1..right - 1] {
+                *cell = (style.horizontal, border);
+            }
+        }
+        for row in buffer.iter_mut().take(bottom - 1).skip(top + 1) {
+            row[left] = (style.vertical, border);
+            row[right - 1] = (style.vertical, border);
+        }
+        buffer[top][left] = (style.up_and_left, border);
+        buffer[top][right - 1] = (style.up_and_right, border);
+        buffer[bottom - 1][left] = (style.down_and_left, border);
+        buffer[bottom - 1][right - 1] = (style.down_and_right, border);
+
+        // Label, backwards when the canvas is mirrored afterwards
+        for (row_index, label) in self.labels.iter().enumerate() {
+            let label_start = left + (self.width - label.len()) / 2;
+            let chars: Vec<char> = match pen.orientation {
+                Orientation::LeftToRight => label.chars().collect(),
+                Orientation::RightToLeft => label.chars().rev().collect(),
+            };
+            for (i, ch) in chars.into_iter().enumerate() {
+                buffer[top + row_index + 1][label_start + i] = (ch, colors.label);
+            }
+        }
+
+        // Left connection, which is part of the connector from the parent
+        if depth > 0 {
+            let parent_colors = NodeColors::new(pen.color_by, depth - 1, false);
+            buffer[origin.y + self.center_y][left] = (
+                style.top_connection.unwrap_or(style.vertical_and_left),
+                parent_colors.connector,
+            );
+        }
+
+        self.render_children(buffer, origin, pen, depth);
+    }
+
+    // The origin (i.e. the top left corner) of each child, given the origin of the current node
+    fn child_origins(
+        &self,
+        origin: &Point2D<usize>,
+        vertical_spacing: usize,
+    ) -> Vec<Point2D<usize>> {
+        let x = origin.x + self.width + HORIZONTAL_LAYER_BUFFER;
+        let mut y = origin.y + self.children_top_offset;
+        self.children
+            .iter()
+            .map(|child| {
+                let child_origin = Point2D { x, y };
+                y += child.overall_height + vertical_spacing;
+                child_origin
+            })
+            .collect()
+    }
+
+    fn render_children(
+        &self,
+        buffer: &mut [Vec<Cell>],
+        origin: &Point2D<usize>,
+        pen: &Pen,
+        depth: usize,
+    ) {
+        if self.children.is_empty() {
+            return;
+        }
+
+        let style = pen.style;
+        let connector = NodeColors::new(pen.color_by, depth, false).connector;
+
+        // Right connection
+        // ┌──────┐
+        // │ Root ├
+        // └──────┘
+        let center = origin.y + self.center_y;
+        let bar_x = origin.x + self.width + 1;
+        buffer[center][bar_x - 2] = (
+            style.bottom_connection.unwrap_or(style.vertical_and_right),
+            connector,
+        );
+        buffer[center][bar_x - 1] = (style.horizontal, connector);
+
+        let child_origins = self.child_origins(origin, pen.vertical_spacing);
+        let child_centers: Vec<usize> = self
+            .children
+            .iter()
+            .zip(&child_origins)
+            .map(|(child, child_origin)| child_origin.y + child.center_y)
+            .collect();
+        let first = child_centers[0];
+        let last = child_centers[child_centers.len() - 1];
+
+        for (y, row) in buffer.iter_mut().enumerate().take(last + 1).skip(first) {
+            let is_child = child_centers.contains(&y);
+            let is_parent = y == center;
+            row[bar_x] = if first == last {
+                // A single child, in line with the parent
+                // ┌──────┐   ┌───────┐
+                // │ Root ├───┤ Child │
+                // └──────┘   └───────┘
+                (style.horizontal, connector)
+            } else if y == first {
+                if is_parent {
+                    (style.down_and_horizontal, connector)
+                } else {
+                    (style.up_and_left, connector)
+                }
+            } else if y == last {
+                if is_parent {
+                    (style.up_and_horizontal, connector)
+                } else {
+                    (style.down_and_left, connector)
+                }
+            } else if is_child && is_parent {
+                (style.vertical_and_horizontal, connector)
+            } else if is_child {
+                (style.vertical_and_right, connector)
+            } else if is_parent {
+                (style.vertical_and_left, connector)
+            } else {
+                (style.vertical, connector)
+            };
+        }
+
+        for (child, (child_origin, child_center)) in self
+            .children
+            .iter()
+            .zip(child_origins.iter().zip(&child_centers))
+        {
+            buffer[*child_center][bar_x + 1] = (style.horizontal, connector);
+            child.render_internal(buffer, child_origin, pen, depth + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn render_thin(root: &TreeNode, vertical_spacing: usize) -> String {
+        render(root, &BoxDrawings::THIN, vertical_spacing, None)
+    }
+
+    #[test]
+    fn test_root() {
+        let root = TreeNode::from_label("root");
+        assert_canonical_eq(
+            r#"
+            ┌──────┐
+            │ root │
+            └──────┘
+            "#,
+            &render_thin(&root, 0),
+        );
+    }
+
+    #[test]
+    fn test_root_with_one_child() {
+        let root = TreeNode::new("root", vec![TreeNode::from_label("child1")]);
+        assert_canonical_eq(
+            r#"
+            ┌──────┐   ┌────────┐
+            │ root ├───┤ child1 │
+            └──────┘   └────────┘
+            "#,
+            &render_thin(&root, 0),
+        );
+    }
+
+    #[test]
+    fn test_root_with_two_children() {
+        let root = TreeNode::new(
+            "root",
+            vec![
+                TreeNode::from_label("child1"),
+                TreeNode::from_label("child2"),
+            ],
+        );
+        assert_canonical_eq(
+            r#"
+                       ┌────────┐
+            ┌──────┐ ┌─┤ child1 │
+            │ root ├─┤ └────────┘
+            └──────┘ │ ┌────────┐
+                     └─┤ child2 │
+                       └────────┘
+            "#,
+            &render_thin(&root, 0),
+        );
+    }
+
+    #[test]
+    fn test_root_with_three_children_and_spacing() {
+        let root = TreeNode::new(
+            "root",
+            vec![
+                TreeNode::from_label("child1"),
+                TreeNode::from_label("child2"),
+                TreeNode::from_label("child3"),
+            ],
+        );
+        assert_canonical_eq(
+            r#"
+                       ┌────────┐
+                     ┌─┤ child1 │
+                     │ └────────┘
+                     │
+            ┌──────┐ │ ┌────────┐
+            │ root ├─┼─┤ child2 │
+            └──────┘ │ └────────┘
+                     │
+                     │ ┌────────┐
+                     └─┤ child3 │
+                       └────────┘
+            "#,
+            &render_thin(&root, 1),
+        );
+    }
+
+    #[test]
+    fn test_tall_root_with_grandchildren() {
+        let root = TreeNode::new(
+            "root\\nwith\\nfour\\nlines",
+            vec![TreeNode::new(
+                "child",
+                vec![TreeNode::from_label("a"), TreeNode::from_label("b")],
+            )],
+        );
+        assert_canonical_eq(
+            r#"
+            ┌───────┐               ┌───┐
+            │ root  │   ┌───────┐ ┌─┤ a │
+            │ with  ├───┤ child ├─┤ └───┘
+            │ four  │   └───────┘ │ ┌───┐
+            │ lines │             └─┤ b │
+            └───────┘               └───┘
+            "#,
+            &render_thin(&root, 0),
+        );
+    }
+
+    #[test]
+    fn test_right_to_left() {
+        let root = TreeNode::new(
+            "root",
+            vec![
+                TreeNode::from_label("child1\\nmore"),
+                TreeNode::from_label("c2"),
+            ],
+        );
+        assert_canonical_eq(
+            r#"
+            ┌────────┐
+            │ child1 ├─┐
+            │  more  │ │ ┌──────┐
+            └────────┘ ├─┤ root │
+                ┌────┐ │ └──────┘
+                │ c2 ├─┘
+                └────┘
+            "#,
+            &BoxedTreeNode::new(&root, 0).render_oriented(
+                &BoxDrawings::THIN,
+                0,
+                None,
+                Orientation::RightToLeft,
+            ),
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::tree::color::{paint_row, ColorBy};
+use crate::tree::horizontal::Orientation;
+use crate::tree::horizontal_boxes::{BoxedTreeNode, Cell};
+use crate::tree::style::BoxDrawings;
+use crate::tree::tree_node::TreeNode;
+use std::cmp::max;
+
+// Render the tree as a mind map, with the root in the middle, the first children on
+// the right and the others on the left, each side growing outwards, e.g.
+// ┌─────────┐              ┌─────────┐
+// │ Child 3 ├─┐ ┌──────┐ ┌─┤ Child 1 │
+// └─────────┘ ├─┤ Root ├─┤ └─────────┘
+// ┌─────────┐ │ └──────┘ │ ┌─────────┐
+// │ Child 4 ├─┘          └─┤ Child 2 │
+// └─────────┘              └─────────┘
+pub fn render(
+    tree_node: &TreeNode,
+    style: &BoxDrawings,
+    vertical_spacing: usize,
+    color_by: Option<ColorBy>,
+) -> String {
+    let (right, left) = tree_node
+        .children
+        .split_at(split_index(&tree_node.children, vertical_spacing));
+    let right = BoxedTreeNode::with_children(tree_node, right, vertical_spacing);
+    let left = BoxedTreeNode::with_children(tree_node, left, vertical_spacing);
+
+    let right_canvas = right.draw(style, vertical_spacing, color_by, Orientation::LeftToRight);
+    let left_canvas = left.draw(style, vertical_spacing, color_by, Orientation::RightToLeft);
+
+    // Both sides share the root, which is on the right edge of the left side
+    let root_left = left.overall_width - left.width;
+    let center_y = max(left.center_y, right.center_y);
+    let (left_top, right_top) = (center_y - left.center_y, center_y - right.center_y);
+    let width = root_left + right.overall_width;
+    let height = max(
+        left_top + left.overall_height,
+        right_top + right.overall_height,
+    );
+
+    let mut canvas: Vec<Vec<Cell>> = vec![vec![(' ', None); width]; height];
+    for (y, row) in right_canvas.into_iter().enumerate() {
+        canvas[right_top + y][root_left..].copy_from_slice(&row);
+    }
+    // The left border of the root, with the connector to the left side, comes from the
+    // left side as well
+    for (y, row) in left_canvas.into_iter().enumerate() {
+        canvas[left_top + y][..=root_left].copy_from_slice(&row[..=root_left]);
+    }
+
+    canvas
+        .iter()
+        .map(|row| paint_row(row))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// The number of children on the right, so that both sides are about the same height.
+// When it is a tie, the extra child goes to the right.
+fn split_index(children: &[TreeNode], vertical_spacing: usize) -> usize {
+    let heights: Vec<usize> = children
+        .iter()
+        .map(|child| BoxedTreeNode::new(child, vertical_spacing).overall_height + vertical_spacing)
+        .collect();
+    let total: usize = heights.iter().sum();
+
+    (0..=children.len())
+        .min_by_key(|&index| {
+            let right: usize = heights[..index].iter().sum();
+            let difference = (right as isize - (total - right) as isize).abs();
+            (difference, children.len() - index)
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn render_thin(root: &TreeNode) -> String {
+        render(root, &BoxDrawings::THIN, 0, None)
+    }
+
+    #[test]
+    fn test_root() {
+        assert_canonical_eq(
+            r#"
+            ┌──────┐
+            │ Root │
+            └──────┘
+            "#,
+            &render_thin(&TreeNode::from_label("Root")),
+        );
+    }
+
+    #[test]
+    fn test_root_with_one_child() {
+        let root = TreeNode::new("Root", vec![TreeNode::from_label("Child")]);
+        assert_canonical_eq(
+            r#"
+            ┌──────┐   ┌───────┐
+            │ Root ├───┤ Child │
+            └──────┘   └───────┘
+            "#,
+            &render_thin(&root),
+        );
+    }
+
+    #[test]
+    fn test_root_with_four_children() {
+        let root = TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::from_label("Child 1"),
+                TreeNode::from_label("Child 2"),
+                TreeNode::from_label("Child 3"),
+                TreeNode::from_label("Child 4"),
+            ],
+        );
+        assert_canonical_eq(
+            r#"
+            ┌─────────┐              ┌─────────┐
+            │ Child 3 ├─┐ ┌──────┐ ┌─┤ Child 1 │
+            └─────────┘ ├─┤ Root ├─┤ └─────────┘
+            ┌─────────┐ │ └──────┘ │ ┌─────────┐
+            │ Child 4 ├─┘          └─┤ Child 2 │
+            └─────────┘              └─────────┘
+            "#,
+            &render_thin(&root),
+        );
+    }
+
+    #[test]
+    fn test_balanced_by_height() {
+        let root = TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new(
+                    "A",
+                    vec![
+                        TreeNode::from_label("1"),
+                        TreeNode::from_label("2"),
+                        TreeNode::from_label("3"),
+                    ],
+                ),
+                TreeNode::from_label("B"),
+                TreeNode::from_label("C"),
+            ],
+        );
+        assert_eq!(split_index(&root.children, 0), 1);
+        assert_eq!(split_index(&root.children[1..], 0), 1);
+        assert_eq!(split_index(&[], 0), 0);
+    }
+}
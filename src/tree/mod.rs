@@ -1,4 +1,9 @@
+pub mod color;
+pub mod fit;
 pub mod horizontal;
+pub mod horizontal_boxes;
+pub mod mindmap;
+pub mod panels;
 pub mod style;
 pub mod tree_node;
 pub mod vertical;
@@ -0,0 +1,296 @@
+use crate::tree::tree_node::TreeNode;
+use crate::tree::vertical::{DrawableTreeNode, LayoutOptions};
+use std::collections::HashSet;
+use std::mem;
+
+// A part of a tree which is too wide to be printed at once. The subtrees cut out of it
+// are replaced with placeholders referring to their own panels, e.g.
+//             ┌──────┐
+//             │ Root │
+//             └──┬───┘
+//       ┌────────┴────────┐
+// ┌─────┴─────┐       ┌───┴───┐
+// │ [see Big] │       │ Small │
+// └───────────┘       └───────┘
+//
+// [Big]
+// ┌─────┐
+// │ Big │
+// └──┬──┘
+//   ...
+#[derive(Debug, PartialEq, Eq)]
+pub struct Panel {
+    // The title matching the placeholder in the parent panel, or None for the root
+    pub title: Option<String>,
+    pub root: TreeNode,
+}
+
+impl Panel {
+    pub fn width(&self, options: &LayoutOptions) -> usize {
+        width(&self.root, options)
+    }
+}
+
+// Cut the tree into panels no wider than the width where possible, by moving the widest
+// subtrees onto their own panels. The panels are in depth-first order, starting with
+// the one of the root.
+pub fn split(root: TreeNode, options: &LayoutOptions, max_width: usize) -> Vec<Panel> {
+    let mut panels = vec![];
+    let mut references = HashSet::new();
+    split_panel(
+        Panel { title: None, root },
+        options,
+        max_width,
+        &mut references,
+        &mut panels,
+    );
+    panels
+}
+
+fn split_panel(
+    mut panel: Panel,
+    options: &LayoutOptions,
+    max_width: usize,
+    references: &mut HashSet<String>,
+    panels: &mut Vec<Panel>,
+) {
+    // The subtrees are replaced in place, so the paths of the other ones stay the same
+    let mut cut: Vec<(Vec<usize>, String, TreeNode)> = vec![];
+    while width(&panel.root, options) > max_width {
+        match widest_subtree(&panel.root, options) {
+            Some(path) => {
+                let reference = unique_reference(subtree(&panel.root, &path), references);
+                let placeholder = placeholder(&reference);
+                let subtree = replace(&mut panel.root, &path, placeholder);
+                cut.push((path, reference, subtree));
+            }
+            None => break,
+        }
+    }
+    cut.sort_by(|a, b| a.0.cmp(&b.0));
+
+    panels.push(panel);
+    for (_, reference, subtree) in cut {
+        let panel = Panel {
+            title: Some(format!("[{}]", reference)),
+            root: subtree,
+        };
+        split_panel(panel, options, max_width, references, panels);
+    }
+}
+
+fn width(node: &TreeNode, options: &LayoutOptions) -> usize {
+    DrawableTreeNode::with_options(node, options).overall_width
+}
+
+// How the panel of the subtree is referred to, i.e. its label on a single line
+fn reference(node: &TreeNode) -> String {
+    node.label_lines().join(" ")
+}
+
+// The reference of the subtree, numbered if another panel already has the same label,
+// e.g. "Misc (2)"
+fn unique_reference(node: &TreeNode, references: &mut HashSet<String>) -> String {
+    let label = reference(node);
+    let mut reference = label.clone();
+    let mut count = 1;
+    while references.contains(&reference) {
+        count += 1;
+        reference = format!("{} ({})", label, count);
+    }
+    references.insert(reference.clone());
+    reference
+}
+
+// The path to the widest subtree below the root, leaving out the leaves and the other
+// subtrees which are not wider than their placeholder, as moving them onto their own
+// panels would not make the tree any narrower
+fn widest_subtree(root: &TreeNode, options: &LayoutOptions) -> Option<Vec<usize>> {
+    fn visit(
+        node: &TreeNode,
+        path: &mut Vec<usize>,
+        options: &LayoutOptions,
+        widest: &mut Option<(usize, Vec<usize>)>,
+    ) {
+        for (idx, child) in node.children.iter().enumerate() {
+            if child.children.is_empty() {
+                continue;
+            }
+            path.push(idx);
+            let child_width = width(child, options);
+            let narrower = width(&placeholder(&reference(child)), options) < child_width;
+            if narrower && widest.as_ref().is_none_or(|(w, _)| child_width > *w) {
+                *widest = Some((child_width, path.clone()));
+            }
+            visit(child, path, options, widest);
+            path.pop();
+        }
+    }
+
+    let mut widest = None;
+    visit(root, &mut vec![], options, &mut widest);
+    widest.map(|(_, path)| path)
+}
+
+fn placeholder(reference: &str) -> TreeNode {
+    TreeNode::from_label(&format!("[see {}]", reference))
+}
+
+fn subtree<'a>(root: &'a TreeNode, path: &[usize]) -> &'a TreeNode {
+    path.iter().fold(root, |node, idx| &node.children[*idx])
+}
+
+// Swap the subtree at the path for the placeholder, and return it
+fn replace(root: &mut TreeNode, path: &[usize], placeholder: TreeNode) -> TreeNode {
+    let (last, parents) = path.split_last().unwrap();
+    let mut parent = root;
+    for idx in parents {
+        parent = &mut parent.children[*idx];
+    }
+    mem::replace(&mut parent.children[*last], placeholder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+    use crate::tree::style::BoxDrawings;
+    use crate::tree::vertical::{render, Orientation};
+
+    fn tree() -> TreeNode {
+        TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new(
+                    "A",
+                    vec![
+                        TreeNode::from_label("A1 Long Label"),
+                        TreeNode::from_label("A2 Long Label"),
+                    ],
+                ),
+                TreeNode::new(
+                    "B",
+                    vec![
+                        TreeNode::new(
+                            "B1",
+                            vec![
+                                TreeNode::from_label("B11 Long Label"),
+                                TreeNode::from_label("B12 Long Label"),
+                            ],
+                        ),
+                        TreeNode::from_label("B2"),
+                    ],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_fits_as_is() {
+        let panels = split(tree(), &LayoutOptions::default(), 200);
+        assert_eq!(
+            panels,
+            vec![Panel {
+                title: None,
+                root: tree()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let panels = split(tree(), &LayoutOptions::default(), 40);
+        let titles: Vec<Option<&str>> = panels.iter().map(|x| x.title.as_deref()).collect();
+        assert_eq!(titles, vec![None, Some("[A]"), Some("[B]"), Some("[B1]")]);
+        for panel in &panels {
+            assert!(width(&panel.root, &LayoutOptions::default()) <= 40);
+        }
+
+        let render = |panel: &Panel| {
+            render(
+                &panel.root,
+                &BoxDrawings::THIN,
+                &LayoutOptions::default(),
+                None,
+                Orientation::TopDown,
+            )
+        };
+        assert_canonical_eq(
+            r#"
+                    ┌──────┐
+                    │ Root │
+                    └──┬───┘
+                 ┌─────┴──────┐
+            ┌────┴────┐  ┌────┴────┐
+            │ [see A] │  │ [see B] │
+            └─────────┘  └─────────┘
+            "#,
+            &render(&panels[0]),
+        );
+        assert_canonical_eq(
+            r#"
+                    ┌───┐
+                    │ B │
+                    └─┬─┘
+                 ┌────┴─────┐
+            ┌────┴─────┐  ┌─┴──┐
+            │ [see B1] │  │ B2 │
+            └──────────┘  └────┘
+            "#,
+            &render(&panels[2]),
+        );
+    }
+
+    #[test]
+    fn test_duplicate_labels() {
+        let misc = || {
+            TreeNode::new(
+                "Misc",
+                vec![
+                    TreeNode::from_label("Some Long Label"),
+                    TreeNode::from_label("Another Long Label"),
+                ],
+            )
+        };
+        let root = TreeNode::new("Root", vec![misc(), misc()]);
+        let panels = split(root, &LayoutOptions::default(), 40);
+        let titles: Vec<Option<&str>> = panels.iter().map(|x| x.title.as_deref()).collect();
+        assert_eq!(titles, vec![None, Some("[Misc]"), Some("[Misc (2)]")]);
+
+        let placeholders: Vec<&str> = panels[0]
+            .root
+            .children
+            .iter()
+            .map(|x| x.label.as_str())
+            .collect();
+        assert_eq!(placeholders, vec!["[see Misc]", "[see Misc (2)]"]);
+    }
+
+    #[test]
+    fn test_narrower_than_placeholder() {
+        // "[see A Long Label]" would be wider than the subtree itself
+        let root = TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new("A Long Label", vec![TreeNode::from_label("B")]),
+                TreeNode::from_label("Another Long Label"),
+            ],
+        );
+        let panels = split(root, &LayoutOptions::default(), 20);
+        assert_eq!(panels.len(), 1);
+        assert_eq!(panels[0].width(&LayoutOptions::default()), 40);
+    }
+
+    #[test]
+    fn test_too_wide_leaves() {
+        let root = TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::from_label("A Very Long Leaf"),
+                TreeNode::from_label("Another Very Long Leaf"),
+            ],
+        );
+        let panels = split(root, &LayoutOptions::default(), 10);
+        assert_eq!(panels.len(), 1);
+    }
+}
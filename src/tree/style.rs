@@ -6,6 +6,7 @@ pub enum Style {
     Chest,
     Balloon,
     Balloon2,
+    Ascii,
 }
 
 pub struct BoxDrawings {
@@ -18,6 +19,8 @@ pub struct BoxDrawings {
     pub vertical_and_horizontal: char,
     pub down_and_horizontal: char,
     pub up_and_horizontal: char,
+    pub vertical_and_right: char,
+    pub vertical_and_left: char,
 
     pub top_connection: Option<char>,
     pub bottom_connection: Option<char>,
@@ -32,6 +35,47 @@ impl BoxDrawings {
             Style::Chest => BoxDrawings::CHEST,
             Style::Balloon => BoxDrawings::BALLOON,
             Style::Balloon2 => BoxDrawings::BALLOON2,
+            Style::Ascii => BoxDrawings::ASCII,
+        }
+    }
+
+    // The same drawings upside down, e.g. '┬' for '┴' and '└' for '┌', so that the
+    // connectors attach to the boxes above rather than below
+    pub fn flipped(&self) -> BoxDrawings {
+        BoxDrawings {
+            up_and_left: self.down_and_left,
+            up_and_right: self.down_and_right,
+            down_and_left: self.up_and_left,
+            down_and_right: self.up_and_right,
+            vertical: self.vertical,
+            horizontal: self.horizontal,
+            vertical_and_horizontal: self.vertical_and_horizontal,
+            down_and_horizontal: self.up_and_horizontal,
+            up_and_horizontal: self.down_and_horizontal,
+            vertical_and_right: self.vertical_and_right,
+            vertical_and_left: self.vertical_and_left,
+            top_connection: self.top_connection,
+            bottom_connection: self.bottom_connection,
+        }
+    }
+
+    // The same drawings mirrored left to right, e.g. '┐' for '┌' and '┤' for '├', so
+    // that the connectors attach to the boxes on the left rather than on the right
+    pub fn mirrored(&self) -> BoxDrawings {
+        BoxDrawings {
+            up_and_left: self.up_and_right,
+            up_and_right: self.up_and_left,
+            down_and_left: self.down_and_right,
+            down_and_right: self.down_and_left,
+            vertical: self.vertical,
+            horizontal: self.horizontal,
+            vertical_and_horizontal: self.vertical_and_horizontal,
+            down_and_horizontal: self.down_and_horizontal,
+            up_and_horizontal: self.up_and_horizontal,
+            vertical_and_right: self.vertical_and_left,
+            vertical_and_left: self.vertical_and_right,
+            top_connection: self.top_connection,
+            bottom_connection: self.bottom_connection,
         }
     }
 
@@ -45,6 +89,8 @@ impl BoxDrawings {
         vertical_and_horizontal: '┼',
         down_and_horizontal: '┬',
         up_and_horizontal: '┴',
+        vertical_and_right: '├',
+        vertical_and_left: '┤',
         top_connection: None,
         bottom_connection: None,
     };
@@ -59,6 +105,8 @@ impl BoxDrawings {
         vertical_and_horizontal: '╋',
         down_and_horizontal: '┳',
         up_and_horizontal: '┻',
+        vertical_and_right: '┣',
+        vertical_and_left: '┫',
         top_connection: None,
         bottom_connection: None,
     };
@@ -73,6 +121,8 @@ impl BoxDrawings {
         vertical_and_horizontal: '╬',
         down_and_horizontal: '╦',
         up_and_horizontal: '╩',
+        vertical_and_right: '╠',
+        vertical_and_left: '╣',
         top_connection: None,
         bottom_connection: None,
     };
@@ -87,6 +137,8 @@ impl BoxDrawings {
         vertical_and_horizontal: '╋',
         down_and_horizontal: '┳',
         up_and_horizontal: '┻',
+        vertical_and_right: '┣',
+        vertical_and_left: '┫',
         top_connection: None,
         bottom_connection: None,
     };
@@ -101,6 +153,8 @@ impl BoxDrawings {
         vertical_and_horizontal: '┼',
         down_and_horizontal: '┬',
         up_and_horizontal: '┴',
+        vertical_and_right: '├',
+        vertical_and_left: '┤',
         top_connection: None,
         bottom_connection: None,
     };
@@ -123,7 +177,139 @@ impl BoxDrawings {
         vertical_and_horizontal: '┼',
         down_and_horizontal: '┬',
         up_and_horizontal: '┴',
+        vertical_and_right: '├',
+        vertical_and_left: '┤',
         top_connection: Some('¤'),
         bottom_connection: Some('¤'),
     };
+    //         +------+
+    //         | Root |
+    //         +--+---+
+    //      +-----+------+
+    // +----+----+  +----+----+
+    // | Child 1 |  | Child 2 |
+    // +---------+  +---------+
+    pub const ASCII: BoxDrawings = BoxDrawings {
+        up_and_left: '+',
+        up_and_right: '+',
+        down_and_left: '+',
+        down_and_right: '+',
+        vertical: '|',
+        horizontal: '-',
+        vertical_and_horizontal: '+',
+        down_and_horizontal: '+',
+        up_and_horizontal: '+',
+        vertical_and_right: '+',
+        vertical_and_left: '+',
+        top_connection: None,
+        bottom_connection: None,
+    };
+}
+
+// The guide lines in front of each node of the horizontal tree
+//
+// Root
+// ├─ Child 1   <- branch
+// │  └─ Child  <- continuation, followed by last_branch
+// └─ Child 2   <- last_branch
+pub struct GuideLines {
+    pub branch: &'static str,
+    pub last_branch: &'static str,
+    pub continuation: &'static str,
+}
+
+impl GuideLines {
+    pub fn new(style: Style) -> GuideLines {
+        match style {
+            Style::Thin => GuideLines::THIN,
+            Style::Thick => GuideLines::THICK,
+            Style::Double => GuideLines::DOUBLE,
+            Style::Chest => GuideLines::THICK,
+            Style::Balloon | Style::Balloon2 => GuideLines::BALLOON,
+            Style::Ascii => GuideLines::ASCII,
+        }
+    }
+
+    // The guide lines of the right to left tree, which follow the labels
+    //          Root
+    //    Child 1 ─┤
+    //    Child 2 ─┘
+    pub fn mirrored(style: Style) -> GuideLines {
+        match style {
+            Style::Thin => GuideLines::THIN_MIRRORED,
+            Style::Thick => GuideLines::THICK_MIRRORED,
+            Style::Double => GuideLines::DOUBLE_MIRRORED,
+            Style::Chest => GuideLines::THICK_MIRRORED,
+            Style::Balloon | Style::Balloon2 => GuideLines::BALLOON_MIRRORED,
+            Style::Ascii => GuideLines::ASCII_MIRRORED,
+        }
+    }
+
+    pub const THIN: GuideLines = GuideLines {
+        branch: "├─ ",
+        last_branch: "└─ ",
+        continuation: "│  ",
+    };
+
+    pub const THICK: GuideLines = GuideLines {
+        branch: "┣━ ",
+        last_branch: "┗━ ",
+        continuation: "┃  ",
+    };
+
+    pub const DOUBLE: GuideLines = GuideLines {
+        branch: "╠═ ",
+        last_branch: "╚═ ",
+        continuation: "║  ",
+    };
+
+    pub const BALLOON: GuideLines = GuideLines {
+        branch: "├─ ",
+        last_branch: "╰─ ",
+        continuation: "│  ",
+    };
+
+    // Root
+    // |- Child 1
+    // |  `- Grandchild
+    // `- Child 2
+    pub const ASCII: GuideLines = GuideLines {
+        branch: "|- ",
+        last_branch: "`- ",
+        continuation: "|  ",
+    };
+
+    pub const THIN_MIRRORED: GuideLines = GuideLines {
+        branch: " ─┤",
+        last_branch: " ─┘",
+        continuation: "  │",
+    };
+
+    pub const THICK_MIRRORED: GuideLines = GuideLines {
+        branch: " ━┫",
+        last_branch: " ━┛",
+        continuation: "  ┃",
+    };
+
+    pub const DOUBLE_MIRRORED: GuideLines = GuideLines {
+        branch: " ═╣",
+        last_branch: " ═╝",
+        continuation: "  ║",
+    };
+
+    pub const BALLOON_MIRRORED: GuideLines = GuideLines {
+        branch: " ─┤",
+        last_branch: " ─╯",
+        continuation: "  │",
+    };
+
+    //             Root
+    //       Child 1 -|
+    // Grandchild -'  |
+    //       Child 2 -'
+    pub const ASCII_MIRRORED: GuideLines = GuideLines {
+        branch: " -|",
+        last_branch: " -'",
+        continuation: "  |",
+    };
 }
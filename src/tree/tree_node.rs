@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use crate::parser::wrap_line;
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct TreeNode {
     pub label: String,
     pub children: Vec<TreeNode>,
@@ -16,7 +18,34 @@ impl TreeNode {
     pub fn new(label: &str, children: Vec<TreeNode>) -> Self {
         TreeNode {
             label: label.to_string(),
-            children: children,
+            children,
+        }
+    }
+
+    // The label split into lines. Line breaks can either be a literal "\n" (e.g. from
+    // the content lines in markdown), or an actual line break added when wrapping.
+    pub fn label_lines(&self) -> Vec<String> {
+        self.label
+            .replace("\\n", "\n")
+            .split('\n')
+            .map(|x| x.to_string())
+            .collect()
+    }
+
+    // The same tree with the lines of all the labels wrapped at the width
+    pub fn wrapped(&self, width: usize) -> TreeNode {
+        TreeNode {
+            label: self
+                .label_lines()
+                .iter()
+                .map(|line| wrap_line(line, Some(width)))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            children: self
+                .children
+                .iter()
+                .map(|child| child.wrapped(width))
+                .collect(),
         }
     }
 }
@@ -1,19 +1,201 @@
 extern crate num;
 
 use self::num::Zero;
+use crate::tree::color::{paint_row, Color, ColorBy, NodeColors};
 use crate::tree::style::BoxDrawings;
 use crate::tree::tree_node::TreeNode;
-use std::cmp::max;
+use std::cmp::{max, min};
+use std::slice;
+
+pub fn render(
+    tree_node: &TreeNode,
+    style: &BoxDrawings,
+    options: &LayoutOptions,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+) -> String {
+    let drawble = DrawableTreeNode::with_options(tree_node, options);
+    drawble.render_oriented(style, color_by, orientation)
+}
+
+pub fn layout(tree_node: &TreeNode, options: &LayoutOptions, orientation: Orientation) -> Layout {
+    let drawble = DrawableTreeNode::with_options(tree_node, options);
+    let layout = drawble.layout();
+    match orientation {
+        Orientation::TopDown => layout,
+        Orientation::BottomUp => layout.flipped(),
+    }
+}
+
+// Render the trees side by side on a single canvas, with the gap between them
+pub fn render_forest(
+    roots: &[TreeNode],
+    style: &BoxDrawings,
+    options: &LayoutOptions,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+    gap: usize,
+) -> String {
+    let drawables: Vec<DrawableTreeNode> = roots
+        .iter()
+        .map(|root| DrawableTreeNode::with_options(root, options))
+        .collect();
+    draw(&drawables, gap, style, color_by, orientation)
+}
+
+// The geometry of the trees side by side, as drawn by `render_forest`
+pub fn layout_forest(
+    roots: &[TreeNode],
+    options: &LayoutOptions,
+    orientation: Orientation,
+    gap: usize,
+) -> Layout {
+    let drawables: Vec<DrawableTreeNode> = roots
+        .iter()
+        .map(|root| DrawableTreeNode::with_options(root, options))
+        .collect();
+    let layout = layout_trees(&drawables, gap);
+    match orientation {
+        Orientation::TopDown => layout,
+        Orientation::BottomUp => layout.flipped(),
+    }
+}
+
+// How to draw an input with several trees
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forest {
+    /// Each tree below the previous one
+    Stacked,
+    /// All the trees next to each other, on the same rows
+    SideBySide,
+    /// All the trees under a synthetic root
+    Root,
+}
+
+// How to place the nodes of the vertical tree
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutOptions {
+    // The horizontal spacing between siblings
+    pub horizontal_spacing: usize,
+
+    // Whether the subtrees are packed together where they do not collide, rather
+    // than placed side by side as rectangles
+    pub compact: bool,
+
+    // Whether the leaves among the children are stacked in a column, as in org charts,
+    // while the other children stay side by side
+    pub stack_leaves: bool,
+
+    // The rows between a parent and its children, where the connectors are drawn. A
+    // single child needs no horizontal bar, so it is one row closer.
+    pub vertical_spacing: usize,
+
+    // Whether all the nodes of the same depth start on the same row, even when the boxes
+    // above them have different heights
+    pub uniform_levels: bool,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        LayoutOptions {
+            horizontal_spacing: 2,
+            compact: false,
+            stack_leaves: false,
+            vertical_spacing: 1,
+            uniform_levels: false,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The root at the top, and the children below
+    TopDown,
+    /// The root at the bottom, and the children above, e.g. for ancestries
+    BottomUp,
+}
+
+// The column of the rail connecting the stacked leaves, relative to the left of the
+// parent box, and the leaves are two columns further
+// ┌─────────┐
+// │ Manager │
+// └─┬───────┘
+//   ├─ ...
+//   ↑
+static STACK_RAIL_OFFSET: usize = 2;
+
+// The columns from the rail to the left of the stacked leaves, i.e. "├─"
+static STACK_LEAF_OFFSET: usize = 2;
+
+// A character on the canvas, and the color to draw it with
+type Cell = (char, Option<Color>);
+
+// The columns occupied on a row, from the first one to the one after the last one
+type Extent = (usize, usize);
 
-pub fn render(tree_node: &TreeNode, style: &BoxDrawings, horizontal_spacing: usize) -> String {
-    let drawble = DrawableTreeNode::new(&tree_node, horizontal_spacing);
-    drawble.render(style, horizontal_spacing)
+// Where the children start below their parents, which depends on the whole tree when
+// the levels are uniform
+struct Levels<'a> {
+    options: &'a LayoutOptions,
+
+    // The height of the tallest box of each depth, or empty if the levels are not uniform
+    heights: Vec<usize>,
+}
+
+impl<'a> Levels<'a> {
+    fn new(root: &TreeNode, options: &'a LayoutOptions) -> Self {
+        fn visit(node: &TreeNode, depth: usize, heights: &mut Vec<usize>) {
+            let height = node.label_lines().len() + 2;
+            match heights.get_mut(depth) {
+                Some(tallest) => *tallest = max(*tallest, height),
+                None => heights.push(height),
+            }
+            for child in &node.children {
+                visit(child, depth + 1, heights);
+            }
+        }
+
+        let mut heights = vec![];
+        if options.uniform_levels {
+            visit(root, 0, &mut heights);
+        }
+        Levels { options, heights }
+    }
+
+    // The row where the children start, relative to the top of their parent. With
+    // several children, the last row of the spacing is the horizontal bar, and the stem
+    // above it gets taller with more spacing:
+    //         ┌──────┐
+    //         │ Root │
+    //         └──┬───┘
+    //            │        <- the stem
+    //      ┌─────┴──────┐ <- the horizontal bar
+    // ┌────┴────┐  ┌────┴────┐
+    // │ Child 1 │  │ Child 2 │
+    // └─────────┘  └─────────┘
+    // With a single child, there is no bar, and the stem is one row shorter, e.g. none
+    // at all with the default spacing. When the levels are uniform, the children start
+    // on the same row as their cousins instead, below the tallest box of the level.
+    fn children_top(&self, node_height: usize, depth: usize, children: usize) -> usize {
+        // The horizontal bar needs a row
+        let spacing = max(self.options.vertical_spacing, 1);
+        match self.heights.get(depth) {
+            Some(level_height) => level_height + spacing,
+            None if children > 1 => node_height + spacing,
+            None => node_height + spacing - 1,
+        }
+    }
 }
 
-static VERTICAL_LAYER_BUFFER: usize = 1;
+// The settings that are the same for all the nodes of the tree being rendered
+struct Pen<'a> {
+    style: &'a BoxDrawings,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+}
 
-#[derive(Debug, Eq, PartialEq)]
-struct Point2D<T> {
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Point2D<T> {
     pub x: T,
     pub y: T,
 }
@@ -32,6 +214,89 @@ where
     }
 }
 
+// The geometry of a rendered tree, so that it can be drawn by other renderers. All the
+// coordinates are in number of characters, with the origin at the top left corner.
+#[derive(Debug)]
+pub struct Layout {
+    // Size of the whole canvas
+    pub width: usize,
+    pub height: usize,
+
+    // All the nodes in depth-first order, starting from the root
+    pub nodes: Vec<NodeLayout>,
+
+    // All the lines connecting the parents with their children
+    pub connectors: Vec<Segment>,
+}
+
+impl Layout {
+    // The same layout upside down, with the root at the bottom. The lines of the labels
+    // stay in the same order.
+    pub fn flipped(self) -> Layout {
+        let height = self.height;
+        let flip = |point: Point2D<usize>| Point2D {
+            x: point.x,
+            y: height - 1 - point.y,
+        };
+        Layout {
+            width: self.width,
+            height,
+            nodes: self
+                .nodes
+                .into_iter()
+                .map(|node| NodeLayout {
+                    rect: Rect {
+                        y: height - node.rect.y - node.rect.height,
+                        ..node.rect
+                    },
+                    center: flip(node.center),
+                    ..node
+                })
+                .collect(),
+            connectors: self
+                .connectors
+                .into_iter()
+                .map(|segment| Segment {
+                    from: flip(segment.from),
+                    to: flip(segment.to),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NodeLayout {
+    // Index of the parent in `Layout::nodes`, or None for the root
+    pub parent: Option<usize>,
+    pub depth: usize,
+
+    // The box of the node, including the borders
+    pub rect: Rect,
+
+    // The lines of the label, from top to bottom
+    pub labels: Vec<String>,
+
+    // The column where the connections are attached, and the middle row of the box
+    pub center: Point2D<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+// A horizontal or vertical line between two cells (both inclusive). The end points
+// attached to a box are on its border, e.g. the '┬' below the parent box.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Segment {
+    pub from: Point2D<usize>,
+    pub to: Point2D<usize>,
+}
+
 #[derive(Debug)]
 pub struct DrawableTreeNode {
     // Horizontal center of the current node
@@ -52,7 +317,8 @@ pub struct DrawableTreeNode {
     // └──────┘
     labels: Vec<String>,
 
-    // Children left offset
+    // The origin of each child, relative to the origin of the current node. The x of
+    // the first one is the additional padding to start the children node
     // ┌────────────────────────┐
     // │ L1 A Very Looong Label │
     // └───────────┬────────────┘
@@ -61,17 +327,39 @@ pub struct DrawableTreeNode {
     //    │ L2A │  │ L2B Long Label │
     //    └─────┘  └────────────────┘
     // ↑↑↑
-    // The additional padding to start the children node
-    chhildren_left_offset: usize,
+    child_offsets: Vec<Point2D<usize>>,
+
+    // The column of the rail connecting the leaves among the children, relative to the
+    // origin of the current node, when they are stacked rather than side by side. The
+    // rail hangs from the current node when all the children are leaves, and from the
+    // horizontal bar otherwise.
+    rail: Option<usize>,
 
     // A list of children
     children: Vec<DrawableTreeNode>,
 }
 
 impl DrawableTreeNode {
+    pub fn with_options(node: &TreeNode, options: &LayoutOptions) -> Self {
+        let levels = Levels::new(node, options);
+        if options.compact {
+            DrawableTreeNode::compact(node, &levels, 0).0
+        } else {
+            DrawableTreeNode::side_by_side(node, &levels, 0)
+        }
+    }
+
     pub fn new(node: &TreeNode, horizontal_spacing: usize) -> Self {
-        let label = node.label.replace("\\n", "\n");
-        let labels: Vec<String> = label.split('\n').map(|x| x.to_string()).collect();
+        let options = LayoutOptions {
+            horizontal_spacing,
+            ..LayoutOptions::default()
+        };
+        DrawableTreeNode::with_options(node, &options)
+    }
+
+    fn side_by_side(node: &TreeNode, levels: &Levels, depth: usize) -> Self {
+        let horizontal_spacing = levels.options.horizontal_spacing;
+        let labels: Vec<String> = node.label_lines();
 
         // A space on both side, and two vertical bars, i.e.:
         // ┌──────┐ <- 1
@@ -87,50 +375,44 @@ impl DrawableTreeNode {
         let drawable_children: Vec<DrawableTreeNode> = node
             .children
             .iter()
-            .map(|x| DrawableTreeNode::new(x, horizontal_spacing))
+            .map(|x| DrawableTreeNode::side_by_side(x, levels, depth + 1))
             .collect();
+        let stack = stacks_leaves(node, levels.options);
+        if stack && node.children.iter().all(|child| child.children.is_empty()) {
+            // The stacked leaves need no horizontal bar, as with a single child
+            let stacked = DrawableTreeNode::stacked(
+                labels,
+                node_width,
+                node_height,
+                levels.children_top(node_height, depth, 1),
+                drawable_children,
+            );
+            return stacked.0;
+        }
 
-        let children_width: usize = if node.children.len() == 0 {
+        let contours = vec![vec![]; drawable_children.len()];
+        let (slots, placements) = slots(&drawable_children, contours, stack);
+        let children_top = levels.children_top(node_height, depth, slots.len());
+
+        let children_width: usize = if slots.is_empty() {
             0
         } else {
             // We put all the children next to each other, with some space in between
-            drawable_children
-                .iter()
-                .map(|child| child.overall_width)
-                .sum::<usize>()
-                + (node.children.len() - 1) * horizontal_spacing
+            slots.iter().map(|slot| slot.overall_width).sum::<usize>()
+                + (slots.len() - 1) * horizontal_spacing
         };
 
         // The height of the current cell and all the children
-        let overall_height = if node.children.len() == 0 {
+        let overall_height = if slots.is_empty() {
             node_height
         } else {
-            let children_height: usize = drawable_children
+            let children_height: usize = slots
                 .iter()
-                .map(|child| child.overall_height)
+                .map(|slot| slot.overall_height)
                 .max()
                 .unwrap_or(0);
 
-            if node.children.len() == 1 {
-                // With single children, no vertical buffer needed.
-                //     ┌──────┐
-                //     │ Root │
-                //     └──┬───┘
-                //   ┌────┴────┐
-                //   │ Child 1 │
-                //   └─────────┘
-                node_height + children_height
-            } else {
-                // More than 1 direct children, vertical buffer needed.
-                //         ┌──────┐
-                //         │ Root │
-                //         └──┬───┘
-                //      ╔═════╩══════╗<- VERTICAL_LAYER_BUFFER
-                // ┌────┴────┐  ┌────┴────┐
-                // │ Child 1 │  │ Child 2 │
-                // └─────────┘  └─────────┘
-                node_height + children_height + VERTICAL_LAYER_BUFFER
-            }
+            children_top + children_height
         };
 
         //    ┌------┐
@@ -143,56 +425,68 @@ impl DrawableTreeNode {
         let center_of_current_box = (node_width - 1) / 2;
 
         // The overall center of the current node and all its children
-        let (center_x, chhildren_left_offset, overall_width) =
-            match (drawable_children.first(), drawable_children.last()) {
-                (Some(first), Some(last)) => {
-                    // If there are children, let's aling the current node to middle of
-                    // all the children.
-                    let connection_bar_width = children_width
-                        - (first.center_x - 1)
-                        - (last.overall_width - last.center_x);
-
-                    // When connection_bar_width is even (e.g. 8), we should add 4.
-                    // When connection_bar_width is odd (e.g. 7), we should add 4.
-                    let center_of_children = (first.center_x - 1) + (connection_bar_width + 1) / 2;
-
-                    // We see which one is bigger, the center of the current node, or the center of the chilren.
-                    // And we use that as the center of the overall node.
-                    //             v
-                    // ┌────────────────────────┐
-                    // │ L1 A Very Looong Label │
-                    // └───────────┬────────────┘
-                    //      ┌──────┴──────┐
-                    //   ┌──┴──┐  ┌───────┴────────┐
-                    //   │ L2A │  │ L2B Long Label │
-                    //   └─────┘  └────────────────┘
-                    let overall_center = max(center_of_current_box, center_of_children);
-
-                    // ┌────────────────────────┐
-                    // │ L1 A Very Looong Label │
-                    // └───────────┬────────────┘
-                    //       ┌─────┴───────┐
-                    //    ┌──┴──┐  ┌───────┴────────┐
-                    //    │ L2A │  │ L2B Long Label │
-                    //    └─────┘  └────────────────┘
-                    // ↑↑↑
-                    // The additional padding to start the children node
-                    let chhildren_left_offset =
-                        max(0, center_of_current_box as i32 - center_of_children as i32) as usize;
-
-                    // Finally, calculate the overall width of the node including all the children.
-                    let current_node_right_bufffer = node_width / 2;
-                    let last_child_right_buffer = last.overall_width - last.center_x;
-                    let connection_bar_right_buffer = connection_bar_width / 2;
-                    let overall_width = max(
-                        overall_center + current_node_right_bufffer + 1,
-                        overall_center + connection_bar_right_buffer + last_child_right_buffer,
-                    );
-
-                    (overall_center, chhildren_left_offset, overall_width)
-                }
-                _ => (center_of_current_box, 0, node_width),
-            };
+        let (center_x, chhildren_left_offset, overall_width) = match (slots.first(), slots.last()) {
+            (Some(first), Some(last)) => {
+                // If there are children, let's aling the current node to middle of
+                // all the children.
+                // The center of the first child may be its leftmost column, e.g. the
+                // rail of stacked leaves, hence the order of the operations.
+                let connection_bar_width =
+                    children_width + 1 - first.center_x - (last.overall_width - last.center_x);
+
+                // When connection_bar_width is even (e.g. 8), we should add 4.
+                // When connection_bar_width is odd (e.g. 7), we should add 4.
+                let center_of_children = first.center_x + (connection_bar_width + 1) / 2 - 1;
+
+                // We see which one is bigger, the center of the current node, or the center of the chilren.
+                // And we use that as the center of the overall node.
+                //             v
+                // ┌────────────────────────┐
+                // │ L1 A Very Looong Label │
+                // └───────────┬────────────┘
+                //      ┌──────┴──────┐
+                //   ┌──┴──┐  ┌───────┴────────┐
+                //   │ L2A │  │ L2B Long Label │
+                //   └─────┘  └────────────────┘
+                let overall_center = max(center_of_current_box, center_of_children);
+
+                // ┌────────────────────────┐
+                // │ L1 A Very Looong Label │
+                // └───────────┬────────────┘
+                //       ┌─────┴───────┐
+                //    ┌──┴──┐  ┌───────┴────────┐
+                //    │ L2A │  │ L2B Long Label │
+                //    └─────┘  └────────────────┘
+                // ↑↑↑
+                // The additional padding to start the children node
+                let chhildren_left_offset =
+                    max(0, center_of_current_box as i32 - center_of_children as i32) as usize;
+
+                // Finally, calculate the overall width of the node including all the children.
+                let current_node_right_bufffer = node_width / 2;
+                let last_child_right_buffer = last.overall_width - last.center_x;
+                let connection_bar_right_buffer = connection_bar_width / 2;
+                let overall_width = max(
+                    overall_center + current_node_right_bufffer + 1,
+                    overall_center + connection_bar_right_buffer + last_child_right_buffer,
+                );
+
+                (overall_center, chhildren_left_offset, overall_width)
+            }
+            _ => (center_of_current_box, 0, node_width),
+        };
+
+        // We put all the children next to each other, with some space in between
+        let mut x = chhildren_left_offset;
+        let slot_offsets: Vec<usize> = slots
+            .iter()
+            .map(|slot| {
+                let offset = x;
+                x += slot.overall_width + horizontal_spacing;
+                offset
+            })
+            .collect();
+        let (child_offsets, rail) = place(&placements, &slot_offsets, children_top);
 
         DrawableTreeNode {
             center_x: center_x,
@@ -201,35 +495,318 @@ impl DrawableTreeNode {
             overall_width: overall_width,
             overall_height: overall_height,
             labels: labels,
-            chhildren_left_offset: chhildren_left_offset,
+            child_offsets: child_offsets,
+            rail,
             children: drawable_children,
         }
     }
 
-    pub fn render(&self, style: &BoxDrawings, horizontal_spacing: usize) -> String {
-        let mut canvas: Vec<Vec<char>> = vec![vec![' '; self.overall_width]; self.overall_height];
+    // Stack the leaves in a column below the node, connected with a rail on the left
+    // rather than with a horizontal bar, as in org charts. The node comes with its
+    // contour, as in `compact`.
+    // ┌─────────┐
+    // │ Manager │
+    // └─┬───────┘
+    //   │ ┌──────────┐
+    //   ├─┤ Report 1 │
+    //   │ └──────────┘
+    //   │ ┌──────────┐
+    //   └─┤ Report 2 │
+    //     └──────────┘
+    fn stacked(
+        labels: Vec<String>,
+        node_width: usize,
+        node_height: usize,
+        children_top: usize,
+        leaves: Vec<DrawableTreeNode>,
+    ) -> (Self, Vec<Extent>) {
+        let x = STACK_RAIL_OFFSET + STACK_LEAF_OFFSET;
+        let mut contour = vec![(0, node_width); node_height];
+        contour.resize(children_top, (STACK_RAIL_OFFSET, STACK_RAIL_OFFSET + 1));
+        let mut child_offsets = vec![];
+        for leaf in &leaves {
+            child_offsets.push(Point2D {
+                x,
+                y: contour.len(),
+            });
+            contour.extend(vec![(STACK_RAIL_OFFSET, x + leaf.width); leaf.height]);
+        }
 
-        self.render_internal(
-            &mut canvas,
-            &Point2D { x: 0, y: 0 },
-            &style,
+        let node = DrawableTreeNode {
+            center_x: (node_width - 1) / 2,
+            width: node_width,
+            height: node_height,
+            overall_width: contour.iter().map(|extent| extent.1).max().unwrap(),
+            overall_height: contour.len(),
+            labels,
+            child_offsets,
+            rail: Some(STACK_RAIL_OFFSET),
+            children: leaves,
+        };
+        (node, contour)
+    }
+
+    // Place each child as close to its previous siblings as possible, as long as they
+    // do not overlap on any row, rather than next to the rectangles of the previous
+    // siblings. A deep subtree can then tuck under a wide but shallow sibling, as in
+    // the tidy trees of Reingold and Tilford.
+    //             ┌──────┐
+    //             │ Root │
+    //             └──┬───┘
+    //         ┌──────┴──────┐
+    // ┌───────┴────────┐  ┌─┴─┐
+    // │ A Wide Sibling │  │ B │
+    // └────────────────┘  └─┬─┘
+    //                ┌──────┴───────┐
+    //                │ A Wide Child │
+    //                └──────────────┘
+    pub fn new_compact(node: &TreeNode, horizontal_spacing: usize) -> Self {
+        let options = LayoutOptions {
             horizontal_spacing,
-        );
+            compact: true,
+            ..LayoutOptions::default()
+        };
+        DrawableTreeNode::with_options(node, &options)
+    }
 
-        canvas
+    // The node together with its contour, i.e. the extent of each row of the node and
+    // all its children
+    fn compact(node: &TreeNode, levels: &Levels, depth: usize) -> (Self, Vec<Extent>) {
+        let horizontal_spacing = levels.options.horizontal_spacing;
+        let labels: Vec<String> = node.label_lines();
+        let node_width = labels.iter().map(|x| x.len()).max().unwrap() + 4;
+        let node_height = labels.len() + 2;
+
+        let (children, contours): (Vec<DrawableTreeNode>, Vec<Vec<Extent>>) = node
+            .children
             .iter()
-            .map(|row| row.iter().collect())
-            .collect::<Vec<String>>()
-            .join("\n")
+            .map(|x| DrawableTreeNode::compact(x, levels, depth + 1))
+            .unzip();
+        let stack = stacks_leaves(node, levels.options);
+        if stack && node.children.iter().all(|child| child.children.is_empty()) {
+            // The stacked leaves need no horizontal bar, as with a single child
+            return DrawableTreeNode::stacked(
+                labels,
+                node_width,
+                node_height,
+                levels.children_top(node_height, depth, 1),
+                children,
+            );
+        }
+
+        if children.is_empty() {
+            let node = DrawableTreeNode {
+                center_x: (node_width - 1) / 2,
+                width: node_width,
+                height: node_height,
+                overall_width: node_width,
+                overall_height: node_height,
+                labels,
+                child_offsets: vec![],
+                rail: None,
+                children,
+            };
+            return (node, vec![(0, node_width); node_height]);
+        }
+
+        let (slots, placements) = slots(&children, contours, stack);
+        let children_top = levels.children_top(node_height, depth, slots.len());
+
+        // The offsets of the children and their overall contour, relative to the first
+        // child, where a child may stick out to the left of the first one
+        let mut offsets: Vec<isize> = vec![];
+        let mut children_contour: Vec<(isize, isize)> = vec![];
+        for contour in slots.iter().map(|slot| &slot.contour) {
+            let offset = children_contour
+                .iter()
+                .zip(contour)
+                .map(|(placed, extent)| placed.1 + horizontal_spacing as isize - extent.0 as isize)
+                .max()
+                .unwrap_or(0);
+            for (row, extent) in contour.iter().enumerate() {
+                let (left, right) = (offset + extent.0 as isize, offset + extent.1 as isize);
+                match children_contour.get_mut(row) {
+                    Some(placed) => *placed = (min(placed.0, left), max(placed.1, right)),
+                    None => children_contour.push((left, right)),
+                }
+            }
+            offsets.push(offset);
+        }
+
+        // The current node is in the middle of the first and the last children
+        let first_center = offsets[0] + slots[0].center_x as isize;
+        let last_center = offsets[offsets.len() - 1] + slots[slots.len() - 1].center_x as isize;
+        let center = first_center + (last_center - first_center) / 2;
+
+        let box_left = center - (node_width as isize - 1) / 2;
+        let mut contour = vec![(box_left, box_left + node_width as isize); node_height];
+        // The stem, and the horizontal bar connecting the children
+        contour.resize(children_top, (center, center + 1));
+        if slots.len() > 1 {
+            contour[children_top - 1] = (first_center, last_center + 1);
+        }
+        contour.extend(children_contour);
+
+        // Shift everything so that the leftmost column is 0
+        let left = contour.iter().map(|extent| extent.0).min().unwrap();
+        let contour: Vec<Extent> = contour
+            .iter()
+            .map(|extent| ((extent.0 - left) as usize, (extent.1 - left) as usize))
+            .collect();
+        let slot_offsets: Vec<usize> = offsets
+            .iter()
+            .map(|offset| (offset - left) as usize)
+            .collect();
+        let (child_offsets, rail) = place(&placements, &slot_offsets, children_top);
+
+        let node = DrawableTreeNode {
+            center_x: (center - left) as usize,
+            width: node_width,
+            height: node_height,
+            overall_width: contour.iter().map(|extent| extent.1).max().unwrap(),
+            overall_height: contour.len(),
+            labels,
+            child_offsets,
+            rail,
+            children,
+        };
+        (node, contour)
+    }
+
+    pub fn layout(&self) -> Layout {
+        layout_trees(slice::from_ref(self), 0)
+    }
+
+    fn layout_internal(&self, layout: &mut Layout, origin: &Point2D<usize>, parent: Option<usize>) {
+        let center_x = origin.x + self.center_x;
+        let bottom = origin.y + self.height - 1;
+        let depth = parent.map_or(0, |parent| layout.nodes[parent].depth + 1);
+
+        let index = layout.nodes.len();
+        layout.nodes.push(NodeLayout {
+            parent,
+            depth,
+            rect: Rect {
+                x: center_x - (self.width - 1) / 2,
+                y: origin.y,
+                width: self.width,
+                height: self.height,
+            },
+            labels: self.labels.clone(),
+            center: Point2D {
+                x: center_x,
+                y: origin.y + (self.height - 1) / 2,
+            },
+        });
+
+        let child_origins = self.child_origins(origin);
+        let drops = self.drops(origin, &child_origins);
+
+        if let Some(rail) = self.box_rail() {
+            self.layout_stacked_children(layout, origin.x + rail, bottom, &child_origins);
+        } else if drops.len() == 1 {
+            //   ┌──────┐
+            //   │ Root │
+            //   └──┬───┘ <- from
+            // ┌────┴────┐ <- to
+            // │ Child 1 │
+            // └─────────┘
+            layout.connectors.push(Segment {
+                from: Point2D {
+                    x: center_x,
+                    y: bottom,
+                },
+                to: Point2D {
+                    x: center_x,
+                    y: child_origins[0].y,
+                },
+            });
+        } else if drops.len() > 1 {
+            //         ┌──────┐
+            //         │ Root │
+            //         └──┬───┘
+            //      ┌─────┴──────┐ <- the horizontal bar
+            // ┌────┴────┐  ┌────┴────┐
+            // │ Child 1 │  │ Child 2 │
+            // └─────────┘  └─────────┘
+            let bar = child_origins[0].y - 1;
+            layout.connectors.push(Segment {
+                from: Point2D {
+                    x: center_x,
+                    y: bottom,
+                },
+                to: Point2D {
+                    x: center_x,
+                    y: bar,
+                },
+            });
+            layout.connectors.push(Segment {
+                from: Point2D {
+                    x: drops[0],
+                    y: bar,
+                },
+                to: Point2D {
+                    x: drops[drops.len() - 1],
+                    y: bar,
+                },
+            });
+            for (child, child_origin) in self.children.iter().zip(&child_origins) {
+                if self.is_stacked(child) {
+                    continue;
+                }
+                let child_center = child_origin.x + child.center_x;
+                layout.connectors.push(Segment {
+                    from: Point2D {
+                        x: child_center,
+                        y: bar,
+                    },
+                    to: Point2D {
+                        x: child_center,
+                        y: child_origin.y,
+                    },
+                });
+            }
+            if let Some(rail) = self.rail {
+                self.layout_stacked_children(layout, origin.x + rail, bar, &child_origins);
+            }
+        }
+
+        for (child, child_origin) in self.children.iter().zip(&child_origins) {
+            child.layout_internal(layout, child_origin, Some(index));
+        }
+    }
+
+    pub fn render(&self, style: &BoxDrawings) -> String {
+        self.render_colored(style, None)
+    }
+
+    // Render the tree with ANSI colors picked by `color_by`, or without colors if None
+    pub fn render_colored(&self, style: &BoxDrawings, color_by: Option<ColorBy>) -> String {
+        self.render_oriented(style, color_by, Orientation::TopDown)
+    }
+
+    // Render the tree with the root at the top or at the bottom. Bottom up, the tree is
+    // drawn with the flipped box drawings and then turned upside down row by row.
+    pub fn render_oriented(
+        &self,
+        style: &BoxDrawings,
+        color_by: Option<ColorBy>,
+        orientation: Orientation,
+    ) -> String {
+        draw(slice::from_ref(self), 0, style, color_by, orientation)
     }
 
     fn render_internal(
         &self,
-        buffer: &mut Vec<Vec<char>>,
+        buffer: &mut Vec<Vec<Cell>>,
         origin: &Point2D<usize>,
-        style: &BoxDrawings,
-        horizontal_spacing: usize,
+        pen: &Pen,
+        depth: usize,
     ) {
+        let style = pen.style;
+        let colors = NodeColors::new(pen.color_by, depth, self.children.is_empty());
+        let border = colors.border;
+
         let left = origin.x + self.center_x - (self.width - 1) / 2;
         let right = left + self.width;
 
@@ -239,8 +816,8 @@ impl DrawableTreeNode {
         // │      │
         // └══════┘
         for x in left + 1..right - 1 {
-            buffer[origin.y][x] = style.horizontal;
-            buffer[origin.y + self.height - 1][x] = style.horizontal;
+            buffer[origin.y][x] = (style.horizontal, border);
+            buffer[origin.y + self.height - 1][x] = (style.horizontal, border);
         }
 
         // Vertical bars
@@ -249,8 +826,8 @@ impl DrawableTreeNode {
         // ║      ║
         // └──────┘
         for y in origin.y + 1..origin.y + self.height - 1 {
-            buffer[y][left] = style.vertical;
-            buffer[y][right - 1] = style.vertical;
+            buffer[y][left] = (style.vertical, border);
+            buffer[y][right - 1] = (style.vertical, border);
         }
 
         // Four corners
@@ -258,141 +835,483 @@ impl DrawableTreeNode {
         // │      │
         // │      │
         // ╚──────╝
-        buffer[origin.y][left] = style.up_and_left;
-        buffer[origin.y][right - 1] = style.up_and_right;
-        buffer[origin.y + self.height - 1][left] = style.down_and_left;
-        buffer[origin.y + self.height - 1][right - 1] = style.down_and_right;
+        buffer[origin.y][left] = (style.up_and_left, border);
+        buffer[origin.y][right - 1] = (style.up_and_right, border);
+        buffer[origin.y + self.height - 1][left] = (style.down_and_left, border);
+        buffer[origin.y + self.height - 1][right - 1] = (style.down_and_right, border);
 
-        // Label
+        // Label, in reverse order when the canvas is turned upside down afterwards
         for (row_index, label) in self.labels.iter().enumerate() {
+            let row = match pen.orientation {
+                Orientation::TopDown => row_index,
+                Orientation::BottomUp => self.labels.len() - 1 - row_index,
+            };
             let label_start = left + (self.width - label.len()) / 2;
             for (i, ch) in label.chars().enumerate() {
-                buffer[origin.y + row_index + 1][label_start + i] = ch;
+                buffer[origin.y + row + 1][label_start + i] = (ch, colors.label);
             }
         }
 
-        // Top connection
-        if origin != &Point2D::<usize>::zero() {
-            buffer[origin.y][origin.x + self.center_x] =
-                style.top_connection.unwrap_or(style.up_and_horizontal);
+        self.render_children(buffer, origin, pen, depth);
+    }
+
+    // The origin (i.e. the top left corner) of each child, given the origin of the current node
+    fn child_origins(&self, origin: &Point2D<usize>) -> Vec<Point2D<usize>> {
+        self.child_offsets
+            .iter()
+            .map(|offset| Point2D {
+                x: origin.x + offset.x,
+                y: origin.y + offset.y,
+            })
+            .collect()
+    }
+
+    // The rail of the stacked leaves when it hangs from the current node itself, i.e.
+    // when all the children are leaves, rather than from the horizontal bar
+    fn box_rail(&self) -> Option<usize> {
+        self.rail
+            .filter(|_| self.children.iter().all(|child| child.children.is_empty()))
+    }
+
+    // Whether the child is one of the stacked leaves
+    fn is_stacked(&self, child: &DrawableTreeNode) -> bool {
+        self.rail.is_some() && child.children.is_empty()
+    }
+
+    // The columns where the connectors go down from the horizontal bar, i.e. the center
+    // of each child, or the rail of the stacked leaves
+    fn drops(&self, origin: &Point2D<usize>, child_origins: &[Point2D<usize>]) -> Vec<usize> {
+        let mut drops = vec![];
+        for (child, child_origin) in self.children.iter().zip(child_origins) {
+            let drop = match self.rail {
+                Some(rail) if child.children.is_empty() => origin.x + rail,
+                _ => child_origin.x + child.center_x,
+            };
+            if !drops.contains(&drop) {
+                drops.push(drop);
+            }
         }
+        drops
+    }
 
-        self.render_children(buffer, origin, style, horizontal_spacing);
+    // The rail from the top row to the last stacked leaf, and the connection to each one
+    //   │ <- from
+    //   │ ┌──────────┐
+    //   ├─┤ Report 1 │
+    //   │ └──────────┘
+    //   │ ┌──────────┐
+    //   └─┤ Report 2 │ <- to
+    //     └──────────┘
+    fn layout_stacked_children(
+        &self,
+        layout: &mut Layout,
+        rail: usize,
+        top: usize,
+        child_origins: &[Point2D<usize>],
+    ) {
+        let leaf_centers: Vec<(usize, &Point2D<usize>)> = self
+            .children
+            .iter()
+            .zip(child_origins)
+            .filter(|(child, _)| self.is_stacked(child))
+            .map(|(child, child_origin)| (child_origin.y + (child.height - 1) / 2, child_origin))
+            .collect();
+        layout.connectors.push(Segment {
+            from: Point2D { x: rail, y: top },
+            to: Point2D {
+                x: rail,
+                y: leaf_centers[leaf_centers.len() - 1].0,
+            },
+        });
+        for (leaf_center, child_origin) in leaf_centers {
+            layout.connectors.push(Segment {
+                from: Point2D {
+                    x: rail,
+                    y: leaf_center,
+                },
+                to: Point2D {
+                    x: child_origin.x,
+                    y: leaf_center,
+                },
+            });
+        }
     }
 
     fn render_children(
         &self,
-        buffer: &mut Vec<Vec<char>>,
+        buffer: &mut Vec<Vec<Cell>>,
         origin: &Point2D<usize>,
-        style: &BoxDrawings,
-        horizontal_spacing: usize,
+        pen: &Pen,
+        depth: usize,
     ) {
         // Draw children
         if self.children.len() == 0 {
             return;
         }
 
+        let style = pen.style;
+        let connector = NodeColors::new(pen.color_by, depth, false).connector;
+        let child_origins = self.child_origins(origin);
+        let center = origin.x + self.center_x;
+        let bottom = origin.y + self.height - 1;
+
+        if let Some(rail) = self.box_rail() {
+            // The rail hangs from the bottom of the current node
+            // └─┬───────┘
+            //   │ ┌──────────┐
+            //   ├─┤ Report 1 │
+            buffer[bottom][origin.x + rail] = (
+                style.bottom_connection.unwrap_or(style.down_and_horizontal),
+                connector,
+            );
+            self.render_stacked_children(
+                buffer,
+                origin.x + rail,
+                bottom,
+                &child_origins,
+                pen,
+                depth,
+            );
+            return;
+        }
+
         // Bottom connection
         // ┌──────┐
         // │ Root │
         // └──╦───┘
-        buffer[origin.y + self.height - 1][origin.x + self.center_x] =
-            style.bottom_connection.unwrap_or(style.down_and_horizontal);
+        buffer[bottom][center] = (
+            style.bottom_connection.unwrap_or(style.down_and_horizontal),
+            connector,
+        );
 
-        let child_origin_y = if self.children.len() > 1 {
-            // More than 1 direct children, vertical buffer needed.
-            //         ┌──────┐
-            //         │ Root │
-            //         └──┬───┘
-            //      ╔═════╩══════╗<- VERTICAL_LAYER_BUFFER
-            // ┌────┴────┐  ┌────┴────┐
-            // │ Child 1 │  │ Child 2 │
-            // └─────────┘  └─────────┘
-            origin.y + self.height + VERTICAL_LAYER_BUFFER
+        // The stem down to the horizontal bar, or to the single child
+        // └──┬───┘
+        //    ║
+        // ┌──┴──...
+        let drops = self.drops(origin, &child_origins);
+        let bar = child_origins[0].y - 1;
+        let stem_end = if drops.len() > 1 {
+            bar
         } else {
-            // With single children, no vertical buffer needed.
-            //     ┌──────┐
-            //     │ Root │
-            //     └──┬───┘
-            //   ┌────┴────┐
-            //   │ Child 1 │
-            //   └─────────┘
-            origin.y + self.height
-        };
-
-        let mut child_origin: Point2D<usize> = Point2D {
-            x: origin.x + self.chhildren_left_offset,
-            y: child_origin_y,
+            child_origins[0].y
         };
+        for row in buffer.iter_mut().take(stem_end).skip(bottom + 1) {
+            row[center] = (style.vertical, connector);
+        }
 
-        for child_id in 0..self.children.len() {
-            let child = &self.children[child_id];
-            child.render_internal(buffer, &child_origin, style, horizontal_spacing);
-
-            if child_id != self.children.len() - 1 {
-                let start = child_origin.x + child.center_x + 1;
-                let end = child_origin.x
-                    + child.overall_width
-                    + horizontal_spacing
-                    + self.children[child_id + 1].center_x;
-                for x in start..end {
-                    if x != origin.x + self.center_x {
-                        buffer[origin.y + self.height][x] = style.horizontal;
-                    } else {
-                        //         ┌──────┐
-                        //         │ Root │
-                        //         └──┬───┘
-                        //      ┌─────╩──────┐
-                        // ┌────┴────┐↑ ┌────┴────┐
-                        // │ Child 1 │  │ Child 2 │
-                        // └─────────┘  └─────────┘
-                        buffer[origin.y + self.height][x] = style.up_and_horizontal;
-                    }
-                }
-                if child_id == 0 {
-                    //         ┌──────┐
-                    //         │ Root │
-                    //         └──┬───┘
-                    //    ->╔─────┴──────┐
-                    // ┌────┴────┐  ┌────┴────┐
-                    // │ Child 1 │  │ Child 2 │
-                    // └─────────┘  └─────────┘
-                    buffer[origin.y + self.height][start - 1] = style.up_and_left;
-                }
+        for (child, child_origin) in self.children.iter().zip(&child_origins) {
+            if self.is_stacked(child) {
+                continue;
+            }
+            child.render_internal(buffer, child_origin, pen, depth + 1);
+
+            // Top connection of the child
+            // ┌──╦───┐
+            // │ Child │
+            // └───────┘
+            buffer[child_origin.y][child_origin.x + child.center_x] = (
+                style.top_connection.unwrap_or(style.up_and_horizontal),
+                connector,
+            );
+        }
 
-                if child_id == self.children.len() - 2 {
+        for (idx, pair) in drops.windows(2).enumerate() {
+            let (start, end) = (pair[0] + 1, pair[1]);
+            for x in start..end {
+                if x != center {
+                    buffer[bar][x] = (style.horizontal, connector);
+                } else {
                     //         ┌──────┐
                     //         │ Root │
                     //         └──┬───┘
-                    //      ┌─────┴──────╗<-
-                    // ┌────┴────┐  ┌────┴────┐
+                    //      ┌─────╩──────┐
+                    // ┌────┴────┐↑ ┌────┴────┐
                     // │ Child 1 │  │ Child 2 │
                     // └─────────┘  └─────────┘
-                    buffer[origin.y + self.height][end] = style.up_and_right;
-                } else if end == origin.x + self.center_x {
-                    //                 ┌──────┐
-                    //                 │ Root │
-                    //                 └──┬───┘
-                    //       ┌────────────╬────────────┐
-                    //  ┌────┴────┐  ┌────┴────┐  ┌────┴────┐
-                    //  │ Child 1 │  │ Child 2 │  │ Child 3 │
-                    //  └─────────┘  └─────────┘  └─────────┘
-                    buffer[origin.y + self.height][end] = style.vertical_and_horizontal;
-                } else {
-                    //                         ┌──────┐
-                    //                         │ Root │
-                    //                      ↓  └──┬───┘  ↓
-                    //         ┌────────────╦─────┴──────╦────────────┐
-                    //    ┌────┴────┐  ┌────┴────┐  ┌────┴────┐  ┌────┴────┐
-                    //    │ Child 1 │  │ Child 2 │  │ Child 3 │  │ Child 4 │
-                    //    └─────────┘  └─────────┘  └─────────┘  └─────────┘
-                    buffer[origin.y + self.height][end] = style.down_and_horizontal;
+                    buffer[bar][x] = (style.up_and_horizontal, connector);
                 }
+            }
+            if idx == 0 {
+                //         ┌──────┐
+                //         │ Root │
+                //         └──┬───┘
+                //    ->╔─────┴──────┐
+                // ┌────┴────┐  ┌────┴────┐
+                // │ Child 1 │  │ Child 2 │
+                // └─────────┘  └─────────┘
+                buffer[bar][start - 1] = (style.up_and_left, connector);
+            }
 
-                child_origin.x += child.overall_width + horizontal_spacing;
+            if idx == drops.len() - 2 {
+                //         ┌──────┐
+                //         │ Root │
+                //         └──┬───┘
+                //      ┌─────┴──────╗<-
+                // ┌────┴────┐  ┌────┴────┐
+                // │ Child 1 │  │ Child 2 │
+                // └─────────┘  └─────────┘
+                buffer[bar][end] = (style.up_and_right, connector);
+            } else if end == center {
+                //                 ┌──────┐
+                //                 │ Root │
+                //                 └──┬───┘
+                //       ┌────────────╬────────────┐
+                //  ┌────┴────┐  ┌────┴────┐  ┌────┴────┐
+                //  │ Child 1 │  │ Child 2 │  │ Child 3 │
+                //  └─────────┘  └─────────┘  └─────────┘
+                buffer[bar][end] = (style.vertical_and_horizontal, connector);
+            } else {
+                //                         ┌──────┐
+                //                         │ Root │
+                //                      ↓  └──┬───┘  ↓
+                //         ┌────────────╦─────┴──────╦────────────┐
+                //    ┌────┴────┐  ┌────┴────┐  ┌────┴────┐  ┌────┴────┐
+                //    │ Child 1 │  │ Child 2 │  │ Child 3 │  │ Child 4 │
+                //    └─────────┘  └─────────┘  └─────────┘  └─────────┘
+                buffer[bar][end] = (style.down_and_horizontal, connector);
             }
         }
+
+        if let Some(rail) = self.rail {
+            // The rail hangs from the horizontal bar, next to the other children
+            //      ┌───────┴───────┐
+            // ┌────┴────┐          │ ┌────────┐
+            // │ Child 1 │          ├─┤ Leaf 1 │
+            self.render_stacked_children(buffer, origin.x + rail, bar, &child_origins, pen, depth);
+        }
     }
+
+    // The rail from below the top row to the last stacked leaf, and the stacked leaves
+    //   │ ┌──────────┐
+    //   ├─┤ Report 1 │
+    //   │ └──────────┘
+    //   │ ┌──────────┐
+    //   └─┤ Report 2 │
+    //     └──────────┘
+    fn render_stacked_children(
+        &self,
+        buffer: &mut Vec<Vec<Cell>>,
+        rail: usize,
+        top: usize,
+        child_origins: &[Point2D<usize>],
+        pen: &Pen,
+        depth: usize,
+    ) {
+        let style = pen.style;
+        let connector = NodeColors::new(pen.color_by, depth, false).connector;
+
+        let leaves: Vec<(&DrawableTreeNode, &Point2D<usize>)> = self
+            .children
+            .iter()
+            .zip(child_origins)
+            .filter(|(child, _)| self.is_stacked(child))
+            .collect();
+        let leaf_centers: Vec<usize> = leaves
+            .iter()
+            .map(|(child, child_origin)| child_origin.y + (child.height - 1) / 2)
+            .collect();
+        let last = leaf_centers[leaf_centers.len() - 1];
+
+        for (y, row) in buffer.iter_mut().enumerate().take(last).skip(top + 1) {
+            row[rail] = if leaf_centers.contains(&y) {
+                (style.vertical_and_right, connector)
+            } else {
+                (style.vertical, connector)
+            };
+        }
+        buffer[last][rail] = (style.down_and_left, connector);
+
+        for ((child, child_origin), leaf_center) in leaves.iter().zip(&leaf_centers) {
+            child.render_internal(buffer, child_origin, pen, depth + 1);
+
+            // Left connection of the leaf
+            buffer[*leaf_center][rail + 1] = (style.horizontal, connector);
+            buffer[*leaf_center][child_origin.x] = (
+                style.top_connection.unwrap_or(style.vertical_and_left),
+                connector,
+            );
+        }
+    }
+}
+
+// The origin of each tree, placed next to each other with the gap in between
+fn tree_origins(trees: &[DrawableTreeNode], gap: usize) -> Vec<Point2D<usize>> {
+    let mut x = 0;
+    trees
+        .iter()
+        .map(|tree| {
+            let origin = Point2D { x, y: 0 };
+            x += tree.overall_width + gap;
+            origin
+        })
+        .collect()
+}
+
+// The size of the canvas holding the trees next to each other
+fn canvas_size(trees: &[DrawableTreeNode], gap: usize) -> (usize, usize) {
+    let widths: usize = trees.iter().map(|tree| tree.overall_width).sum();
+    let gaps = gap * trees.len().saturating_sub(1);
+    let height = trees
+        .iter()
+        .map(|tree| tree.overall_height)
+        .max()
+        .unwrap_or(0);
+    (widths + gaps, height)
+}
+
+fn draw(
+    trees: &[DrawableTreeNode],
+    gap: usize,
+    style: &BoxDrawings,
+    color_by: Option<ColorBy>,
+    orientation: Orientation,
+) -> String {
+    let flipped;
+    let style = match orientation {
+        Orientation::TopDown => style,
+        Orientation::BottomUp => {
+            flipped = style.flipped();
+            &flipped
+        }
+    };
+    let pen = Pen {
+        style,
+        color_by,
+        orientation,
+    };
+
+    let (width, height) = canvas_size(trees, gap);
+    let mut canvas: Vec<Vec<Cell>> = vec![vec![(' ', None); width]; height];
+
+    for (tree, origin) in trees.iter().zip(tree_origins(trees, gap)) {
+        tree.render_internal(&mut canvas, &origin, &pen, 0);
+    }
+
+    // The trees are drawn top down, and then turned upside down, so that they are aligned
+    // at the roots either way
+    if orientation == Orientation::BottomUp {
+        canvas.reverse();
+    }
+
+    canvas
+        .iter()
+        .map(|row| paint_row(row))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn layout_trees(trees: &[DrawableTreeNode], gap: usize) -> Layout {
+    let (width, height) = canvas_size(trees, gap);
+    let mut layout = Layout {
+        width,
+        height,
+        nodes: vec![],
+        connectors: vec![],
+    };
+    for (tree, origin) in trees.iter().zip(tree_origins(trees, gap)) {
+        tree.layout_internal(&mut layout, &origin, None);
+    }
+    layout
+}
+
+// Whether the leaves among the children are stacked in a column, which takes at least
+// two of them
+fn stacks_leaves(node: &TreeNode, options: &LayoutOptions) -> bool {
+    options.stack_leaves
+        && node
+            .children
+            .iter()
+            .filter(|child| child.children.is_empty())
+            .count()
+            > 1
+}
+
+// A column of the children below the horizontal bar, i.e. a child with all its
+// children, or the stacked leaves hanging from their rail as if they were one child
+//      ┌───────┴───────┐
+// ┌────┴────┐          │ ┌────────┐
+// │ Child 1 │          ├─┤ Leaf 1 │
+// └────┬────┘          │ └────────┘
+//     ...              │ ┌────────┐
+//                      └─┤ Leaf 2 │
+//                        └────────┘
+struct Slot {
+    center_x: usize,
+    overall_width: usize,
+    overall_height: usize,
+    contour: Vec<Extent>,
+}
+
+// The slot of a child, and the offset of the child in the slot
+type Placement = (usize, Point2D<usize>);
+
+// Group the children into slots, the stacked leaves going together where the first one
+// is. The contours are those of the children, as in `compact`.
+fn slots(
+    children: &[DrawableTreeNode],
+    contours: Vec<Vec<Extent>>,
+    stack: bool,
+) -> (Vec<Slot>, Vec<Placement>) {
+    let mut slots: Vec<Slot> = vec![];
+    let mut placements = vec![];
+    let mut leaves = None;
+    for (child, contour) in children.iter().zip(contours) {
+        if !(stack && child.children.is_empty()) {
+            placements.push((slots.len(), Point2D { x: 0, y: 0 }));
+            slots.push(Slot {
+                center_x: child.center_x,
+                overall_width: child.overall_width,
+                overall_height: child.overall_height,
+                contour,
+            });
+            continue;
+        }
+
+        let index = *leaves.get_or_insert(slots.len());
+        if index == slots.len() {
+            slots.push(Slot {
+                center_x: 0,
+                overall_width: 0,
+                overall_height: 0,
+                contour: vec![],
+            });
+        }
+        let slot = &mut slots[index];
+        let right = STACK_LEAF_OFFSET + child.width;
+        placements.push((
+            index,
+            Point2D {
+                x: STACK_LEAF_OFFSET,
+                y: slot.overall_height,
+            },
+        ));
+        slot.overall_width = max(slot.overall_width, right);
+        slot.overall_height += child.height;
+        slot.contour.extend(vec![(0, right); child.height]);
+    }
+    (slots, placements)
+}
+
+// The offsets of the children given those of the slots, and the column of the rail if
+// the leaves are stacked
+fn place(
+    placements: &[Placement],
+    slot_offsets: &[usize],
+    children_top: usize,
+) -> (Vec<Point2D<usize>>, Option<usize>) {
+    let child_offsets = placements
+        .iter()
+        .map(|(slot, offset)| Point2D {
+            x: slot_offsets[*slot] + offset.x,
+            y: children_top + offset.y,
+        })
+        .collect();
+    // Only the stacked leaves are offset in their slot
+    let rail = placements
+        .iter()
+        .find(|(_, offset)| offset.x == STACK_LEAF_OFFSET)
+        .map(|(slot, _)| slot_offsets[*slot]);
+    (child_offsets, rail)
 }
 
 #[cfg(test)]
@@ -407,7 +1326,7 @@ mod layout_tests {
     fn test_root() {
         let root = TreeNode::from_label("root");
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
         let expected = r#"
         ┌──────┐
         │ root │
@@ -421,7 +1340,7 @@ mod layout_tests {
         let root = TreeNode::new("root", vec![child1]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
          ┌──────┐
@@ -440,7 +1359,7 @@ mod layout_tests {
         let root = TreeNode::new("root", vec![child1, child2]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
                ┌──────┐
@@ -461,7 +1380,7 @@ mod layout_tests {
         let root = TreeNode::new("root", vec![child1, child2, child3]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
                      ┌──────┐
@@ -486,7 +1405,7 @@ mod layout_tests {
         let root = TreeNode::new("root", vec![child1, child2]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
                                  ┌──────┐
@@ -507,7 +1426,7 @@ mod layout_tests {
     fn test_multi_line_label() {
         let root = TreeNode::from_label("Root\\nNode");
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
         let expected = r#"
         ┌──────┐
         │ Root │
@@ -528,7 +1447,7 @@ mod layout_tests {
         let root = TreeNode::new("root\\nnode", vec![child1, child2]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
                                  ┌──────┐
@@ -554,7 +1473,7 @@ mod layout_tests {
         let root = TreeNode::new("a long root node", vec![child1]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
         ┌──────────────────┐
@@ -569,7 +1488,7 @@ mod layout_tests {
         let root = TreeNode::new("a long root node", children);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
         ┌──────────────────┐
@@ -589,7 +1508,7 @@ mod layout_tests {
         let root = TreeNode::new("a long root node", children);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
         ┌──────────────────┐
@@ -609,7 +1528,7 @@ mod layout_tests {
         let root = TreeNode::new("L1 A Very Looong Label", vec![child_l2a, child_l2b]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
         ┌────────────────────────┐   
@@ -629,7 +1548,7 @@ mod layout_tests {
         let root = TreeNode::new("L1 A Very Looong Label", vec![child_l2a, child_l2b]);
 
         let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
-        let result = drawable_root.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render(&BoxDrawings::THIN);
 
         let expected = r#"
        ┌────────────────────────┐   
@@ -641,6 +1560,255 @@ mod layout_tests {
     └────────────────┘  └─────┘"#;
         assert_canonical_eq(&result, &expected);
     }
+
+    #[test]
+    fn test_bottom_up() {
+        let child1 = TreeNode::new(
+            "child1\\nnode",
+            vec![TreeNode::from_label("a"), TreeNode::from_label("b")],
+        );
+        let child2 = TreeNode::from_label("child2");
+        let root = TreeNode::new("root", vec![child1, child2]);
+
+        let result = render(
+            &root,
+            &BoxDrawings::THIN,
+            &LayoutOptions::default(),
+            None,
+            Orientation::BottomUp,
+        );
+
+        let expected = r#"
+        ┌───┐  ┌───┐
+        │ a │  │ b │
+        └─┬─┘  └─┬─┘
+          └──┬───┘
+         ┌───┴────┐
+         │ child1 │   ┌────────┐
+         │  node  │   │ child2 │
+         └───┬────┘   └───┬────┘
+             └─────┬──────┘
+                ┌──┴───┐
+                │ root │
+                └──────┘"#;
+        assert_canonical_eq(&result, &expected);
+    }
+}
+
+#[cfg(test)]
+mod compact_tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_tuck_under_sibling() {
+        let root = TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::from_label("A Wide Sibling"),
+                TreeNode::new("B", vec![TreeNode::from_label("A Wide Child")]),
+            ],
+        );
+        let drawable_root = DrawableTreeNode::new_compact(&root, HORIZONTAL_CHILDREN_SPACING);
+
+        let expected = r#"
+                    ┌──────┐
+                    │ Root │
+                    └──┬───┘
+                ┌──────┴──────┐
+        ┌───────┴────────┐  ┌─┴─┐
+        │ A Wide Sibling │  │ B │
+        └────────────────┘  └─┬─┘
+                       ┌──────┴───────┐
+                       │ A Wide Child │
+                       └──────────────┘"#;
+        assert_canonical_eq(&drawable_root.render(&BoxDrawings::THIN), expected);
+        assert_eq!(drawable_root.overall_width, 31);
+    }
+
+    #[test]
+    fn test_subtree_sticking_out_to_the_left() {
+        let root = TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::from_label("A"),
+                TreeNode::new("B", vec![TreeNode::from_label("A Very Much Wider Child")]),
+            ],
+        );
+        let drawable_root = DrawableTreeNode::new_compact(&root, HORIZONTAL_CHILDREN_SPACING);
+
+        let expected = r#"
+              ┌──────┐
+              │ Root │
+              └──┬───┘
+              ┌──┴───┐
+            ┌─┴─┐  ┌─┴─┐
+            │ A │  │ B │
+            └───┘  └─┬─┘
+        ┌────────────┴────────────┐
+        │ A Very Much Wider Child │
+        └─────────────────────────┘"#;
+        assert_canonical_eq(&drawable_root.render(&BoxDrawings::THIN), expected);
+    }
+
+    #[test]
+    fn test_same_as_regular_without_interleaving() {
+        let root = TreeNode::new(
+            "root",
+            vec![
+                TreeNode::from_label("child1"),
+                TreeNode::from_label("a long child2"),
+                TreeNode::from_label("child3"),
+            ],
+        );
+        assert_eq!(
+            DrawableTreeNode::new_compact(&root, HORIZONTAL_CHILDREN_SPACING)
+                .render(&BoxDrawings::THIN),
+            DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING).render(&BoxDrawings::THIN),
+        );
+    }
+}
+
+#[cfg(test)]
+mod color_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_colored_by_depth() {
+        let root = TreeNode::new("root", vec![TreeNode::from_label("child")]);
+        let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable_root.render_colored(&BoxDrawings::THIN, Some(ColorBy::Depth));
+
+        let expected = [
+            " \x1b[36m┌──────┐\x1b[0m",
+            " \x1b[36m│ root │\x1b[0m",
+            " \x1b[36m└──┬───┘\x1b[0m",
+            "\x1b[32m┌───\x1b[0m\x1b[36m┴\x1b[0m\x1b[32m───┐\x1b[0m",
+            "\x1b[32m│ child │\x1b[0m",
+            "\x1b[32m└───────┘\x1b[0m",
+        ];
+        assert_eq!(result, expected.join("\n"));
+    }
+
+    #[test]
+    fn test_render_without_colors() {
+        let root = TreeNode::new("root", vec![TreeNode::from_label("child")]);
+        let drawable_root = DrawableTreeNode::new(&root, HORIZONTAL_CHILDREN_SPACING);
+        assert_eq!(
+            drawable_root.render_colored(&BoxDrawings::THIN, None),
+            drawable_root.render(&BoxDrawings::THIN),
+        );
+    }
+}
+
+#[cfg(test)]
+mod geometry_tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_root_with_two_children() {
+        //         ┌──────┐
+        //         │ root │
+        //         └──┬───┘
+        //      ┌─────┴─────┐
+        //  ┌───┴────┐  ┌───┴────┐
+        //  │ child1 │  │ child2 │
+        //  └────────┘  └────────┘
+        let root = TreeNode::new(
+            "root",
+            vec![
+                TreeNode::from_label("child1"),
+                TreeNode::from_label("child2"),
+            ],
+        );
+        let layout = layout(&root, &LayoutOptions::default(), Orientation::TopDown);
+
+        assert_eq!((layout.width, layout.height), (22, 7));
+
+        let rects: Vec<Rect> = layout.nodes.iter().map(|node| node.rect).collect();
+        assert_eq!(
+            rects,
+            vec![
+                Rect {
+                    x: 7,
+                    y: 0,
+                    width: 8,
+                    height: 3
+                },
+                Rect {
+                    x: 0,
+                    y: 4,
+                    width: 10,
+                    height: 3
+                },
+                Rect {
+                    x: 12,
+                    y: 4,
+                    width: 10,
+                    height: 3
+                },
+            ]
+        );
+
+        let parents: Vec<Option<usize>> = layout.nodes.iter().map(|node| node.parent).collect();
+        assert_eq!(parents, vec![None, Some(0), Some(0)]);
+        assert_eq!(layout.nodes[1].labels, vec!["child1"]);
+        assert_eq!(layout.nodes[0].center, Point2D { x: 10, y: 1 });
+
+        assert_eq!(
+            layout.connectors,
+            vec![
+                Segment {
+                    from: Point2D { x: 10, y: 2 },
+                    to: Point2D { x: 10, y: 3 }
+                },
+                Segment {
+                    from: Point2D { x: 4, y: 3 },
+                    to: Point2D { x: 16, y: 3 }
+                },
+                Segment {
+                    from: Point2D { x: 4, y: 3 },
+                    to: Point2D { x: 4, y: 4 }
+                },
+                Segment {
+                    from: Point2D { x: 16, y: 3 },
+                    to: Point2D { x: 16, y: 4 }
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layout_bottom_up() {
+        let root = TreeNode::new("root", vec![TreeNode::from_label("child")]);
+        let layout = layout(&root, &LayoutOptions::default(), Orientation::BottomUp);
+
+        let rects: Vec<Rect> = layout.nodes.iter().map(|node| node.rect).collect();
+        assert_eq!(
+            rects,
+            vec![
+                Rect {
+                    x: 1,
+                    y: 3,
+                    width: 8,
+                    height: 3
+                },
+                Rect {
+                    x: 0,
+                    y: 0,
+                    width: 9,
+                    height: 3
+                },
+            ]
+        );
+        assert_eq!(
+            layout.connectors,
+            vec![Segment {
+                from: Point2D { x: 4, y: 3 },
+                to: Point2D { x: 4, y: 2 }
+            }]
+        );
+    }
 }
 
 #[cfg(test)]
@@ -661,7 +1829,7 @@ mod style_tests {
 
     #[rstest]
     fn test_style_thin(drawable: DrawableTreeNode) {
-        let result = drawable.render(&BoxDrawings::THIN, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable.render(&BoxDrawings::THIN);
         let expected = r#"
                ┌──────┐
                │ root │
@@ -675,7 +1843,7 @@ mod style_tests {
 
     #[rstest]
     fn test_style_thick(drawable: DrawableTreeNode) {
-        let result = drawable.render(&BoxDrawings::THICK, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable.render(&BoxDrawings::THICK);
         let expected = r#"
                ┏━━━━━━┓
                ┃ root ┃
@@ -689,7 +1857,7 @@ mod style_tests {
 
     #[rstest]
     fn test_style_double(drawable: DrawableTreeNode) {
-        let result = drawable.render(&BoxDrawings::DOUBLE, HORIZONTAL_CHILDREN_SPACING);
+        let result = drawable.render(&BoxDrawings::DOUBLE);
         let expected = r#"
                ╔══════╗
                ║ root ║
@@ -701,24 +1869,37 @@ mod style_tests {
         assert_canonical_eq(&result, &expected);
     }
 
+    #[rstest]
+    fn test_style_ascii(drawable: DrawableTreeNode) {
+        let result = drawable.render(&BoxDrawings::ASCII);
+        let expected = r#"
+               +------+
+               | root |
+               +--+---+
+            +-----+-----+
+        +---+----+  +---+----+
+        | child1 |  | child2 |
+        +--------+  +--------+"#;
+        assert_canonical_eq(&result, &expected);
+    }
+
     #[rstest]
     fn test_style_with_top_connection(drawable: DrawableTreeNode) {
-        let result = drawable.render(
-            &BoxDrawings {
-                up_and_left: '┌',
-                up_and_right: '┐',
-                down_and_left: '└',
-                down_and_right: '┘',
-                vertical: '│',
-                horizontal: '─',
-                vertical_and_horizontal: '┼',
-                down_and_horizontal: '┬',
-                up_and_horizontal: '┴',
-                top_connection: Some('▼'),
-                bottom_connection: None,
-            },
-            HORIZONTAL_CHILDREN_SPACING,
-        );
+        let result = drawable.render(&BoxDrawings {
+            up_and_left: '┌',
+            up_and_right: '┐',
+            down_and_left: '└',
+            down_and_right: '┘',
+            vertical: '│',
+            horizontal: '─',
+            vertical_and_horizontal: '┼',
+            down_and_horizontal: '┬',
+            up_and_horizontal: '┴',
+            vertical_and_right: '├',
+            vertical_and_left: '┤',
+            top_connection: Some('▼'),
+            bottom_connection: None,
+        });
         let expected = r#"
                ┌──────┐
                │ root │
@@ -730,3 +1911,331 @@ mod style_tests {
         assert_canonical_eq(&result, &expected);
     }
 }
+
+#[cfg(test)]
+mod stack_tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn stack_leaves(compact: bool) -> LayoutOptions {
+        LayoutOptions {
+            compact,
+            stack_leaves: true,
+            ..LayoutOptions::default()
+        }
+    }
+
+    fn org_chart() -> TreeNode {
+        TreeNode::new(
+            "CEO",
+            vec![
+                TreeNode::new(
+                    "Engineering",
+                    vec![
+                        TreeNode::from_label("Alice"),
+                        TreeNode::from_label("Bob Long Name"),
+                    ],
+                ),
+                TreeNode::new("Sales", vec![TreeNode::from_label("Carol")]),
+                TreeNode::from_label("Legal"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_stack_leaves() {
+        let result = render(
+            &org_chart(),
+            &BoxDrawings::THIN,
+            &stack_leaves(false),
+            None,
+            Orientation::TopDown,
+        );
+        let expected = r#"
+                           ┌─────┐
+                           │ CEO │
+                           └──┬──┘
+               ┌──────────────┴────┬──────────┐
+        ┌──────┴──────┐        ┌───┴───┐  ┌───┴───┐
+        │ Engineering │        │ Sales │  │ Legal │
+        └─┬───────────┘        └───┬───┘  └───────┘
+          │ ┌───────┐          ┌───┴───┐
+          ├─┤ Alice │          │ Carol │
+          │ └───────┘          └───────┘
+          │ ┌───────────────┐
+          └─┤ Bob Long Name │
+            └───────────────┘"#;
+        assert_canonical_eq(&result, &expected);
+    }
+
+    #[test]
+    fn test_stack_leaves_compact() {
+        let result = render(
+            &org_chart(),
+            &BoxDrawings::THIN,
+            &stack_leaves(true),
+            None,
+            Orientation::TopDown,
+        );
+        let expected = r#"
+                        ┌─────┐
+                        │ CEO │
+                        └──┬──┘
+               ┌───────────┴─┬──────────┐
+        ┌──────┴──────┐  ┌───┴───┐  ┌───┴───┐
+        │ Engineering │  │ Sales │  │ Legal │
+        └─┬───────────┘  └───┬───┘  └───────┘
+          │ ┌───────┐    ┌───┴───┐
+          ├─┤ Alice │    │ Carol │
+          │ └───────┘    └───────┘
+          │ ┌───────────────┐
+          └─┤ Bob Long Name │
+            └───────────────┘"#;
+        assert_canonical_eq(&result, &expected);
+    }
+
+    #[test]
+    fn test_stack_mixed_leaves() {
+        // The leaves hang from the horizontal bar, next to the other children
+        let root = TreeNode::new(
+            "CEO",
+            vec![
+                TreeNode::new("Sales", vec![TreeNode::from_label("Carol")]),
+                TreeNode::from_label("Legal"),
+                TreeNode::from_label("Human Resources"),
+            ],
+        );
+        for compact in [false, true] {
+            let result = render(
+                &root,
+                &BoxDrawings::THIN,
+                &stack_leaves(compact),
+                None,
+                Orientation::TopDown,
+            );
+            let expected = r#"
+                    ┌─────┐
+                    │ CEO │
+                    └──┬──┘
+                    ┌──┴───┐
+                ┌───┴───┐  │ ┌───────┐
+                │ Sales │  ├─┤ Legal │
+                └───┬───┘  │ └───────┘
+                ┌───┴───┐  │ ┌─────────────────┐
+                │ Carol │  └─┤ Human Resources │
+                └───────┘    └─────────────────┘"#;
+            assert_canonical_eq(&result, &expected);
+        }
+    }
+
+    #[test]
+    fn test_stack_leaves_layout() {
+        let root = TreeNode::new(
+            "M",
+            vec![TreeNode::from_label("A"), TreeNode::from_label("B")],
+        );
+        let layout = layout(&root, &stack_leaves(false), Orientation::TopDown);
+        assert_eq!((layout.width, layout.height), (9, 9));
+        assert_eq!(
+            layout.nodes[2].rect,
+            Rect {
+                x: 4,
+                y: 6,
+                width: 5,
+                height: 3
+            }
+        );
+        assert_eq!(
+            layout.connectors,
+            vec![
+                Segment {
+                    from: Point2D { x: 2, y: 2 },
+                    to: Point2D { x: 2, y: 7 },
+                },
+                Segment {
+                    from: Point2D { x: 2, y: 4 },
+                    to: Point2D { x: 4, y: 4 },
+                },
+                Segment {
+                    from: Point2D { x: 2, y: 7 },
+                    to: Point2D { x: 4, y: 7 },
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod forest_tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn forest() -> Vec<TreeNode> {
+        vec![
+            TreeNode::new(
+                "Root 1",
+                vec![TreeNode::from_label("A"), TreeNode::from_label("B")],
+            ),
+            TreeNode::from_label("Root 2"),
+        ]
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let result = render_forest(
+            &forest(),
+            &BoxDrawings::THIN,
+            &LayoutOptions::default(),
+            None,
+            Orientation::TopDown,
+            3,
+        );
+        let expected = r#"
+             ┌────────┐    ┌────────┐
+             │ Root 1 │    │ Root 2 │
+             └───┬────┘    └────────┘
+              ┌──┴───┐
+            ┌─┴─┐  ┌─┴─┐
+            │ A │  │ B │
+            └───┘  └───┘"#;
+        assert_canonical_eq(&result, &expected);
+    }
+
+    #[test]
+    fn test_side_by_side_bottom_up() {
+        let result = render_forest(
+            &forest(),
+            &BoxDrawings::THIN,
+            &LayoutOptions::default(),
+            None,
+            Orientation::BottomUp,
+            3,
+        );
+        let expected = r#"
+            ┌───┐  ┌───┐
+            │ A │  │ B │
+            └─┬─┘  └─┬─┘
+              └──┬───┘
+             ┌───┴────┐    ┌────────┐
+             │ Root 1 │    │ Root 2 │
+             └────────┘    └────────┘"#;
+        assert_canonical_eq(&result, &expected);
+    }
+
+    #[test]
+    fn test_layout_side_by_side() {
+        let layout = layout_forest(
+            &forest(),
+            &LayoutOptions::default(),
+            Orientation::TopDown,
+            3,
+        );
+        assert_eq!((layout.width, layout.height), (25, 7));
+        let roots: Vec<&NodeLayout> = layout
+            .nodes
+            .iter()
+            .filter(|node| node.parent.is_none())
+            .collect();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(
+            roots[1].rect,
+            Rect {
+                x: 15,
+                y: 0,
+                width: 10,
+                height: 3
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod level_tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn tree() -> TreeNode {
+        TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new("A\\nB", vec![TreeNode::from_label("C")]),
+                TreeNode::new("D", vec![TreeNode::from_label("E")]),
+            ],
+        )
+    }
+
+    fn render_with(options: &LayoutOptions) -> String {
+        render(
+            &tree(),
+            &BoxDrawings::THIN,
+            options,
+            None,
+            Orientation::TopDown,
+        )
+    }
+
+    #[test]
+    fn test_level_spacing() {
+        let options = LayoutOptions {
+            vertical_spacing: 2,
+            ..LayoutOptions::default()
+        };
+        let expected = r#"
+              ┌──────┐
+              │ Root │
+              └──┬───┘
+                 │
+              ┌──┴───┐
+            ┌─┴─┐  ┌─┴─┐
+            │ A │  │ D │
+            │ B │  └─┬─┘
+            └─┬─┘    │
+              │    ┌─┴─┐
+            ┌─┴─┐  │ E │
+            │ C │  └───┘
+            └───┘"#;
+        assert_canonical_eq(&render_with(&options), &expected);
+    }
+
+    #[test]
+    fn test_uniform_levels() {
+        let options = LayoutOptions {
+            uniform_levels: true,
+            ..LayoutOptions::default()
+        };
+        let expected = r#"
+              ┌──────┐
+              │ Root │
+              └──┬───┘
+              ┌──┴───┐
+            ┌─┴─┐  ┌─┴─┐
+            │ A │  │ D │
+            │ B │  └─┬─┘
+            └─┬─┘    │
+              │      │
+            ┌─┴─┐  ┌─┴─┐
+            │ C │  │ E │
+            └───┘  └───┘"#;
+        assert_canonical_eq(&render_with(&options), &expected);
+    }
+
+    #[test]
+    fn test_layout_level_spacing() {
+        let options = LayoutOptions {
+            vertical_spacing: 3,
+            ..LayoutOptions::default()
+        };
+        let layout = layout(&tree(), &options, Orientation::TopDown);
+        // The stem from the root down to the horizontal bar, right above the children
+        assert_eq!(
+            layout.connectors[0],
+            Segment {
+                from: Point2D { x: 5, y: 2 },
+                to: Point2D { x: 5, y: 5 },
+            }
+        );
+        assert_eq!(layout.nodes[1].rect.y, 6);
+        // A single child is one row closer
+        assert_eq!(layout.nodes[2].rect.y, 6 + 4 + 2);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::tree::tree_node::TreeNode;
+
+// Quote a field when it contains a delimiter, a quote or a line break, as in RFC 4180
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn label(node: &TreeNode) -> String {
+    field(&node.label_lines().join("\n"))
+}
+
+// Render the forest as an edge list, with one node per row in depth-first order, e.g.
+// id,parent_id,depth,label
+// 0,,0,Root
+// 1,0,1,Child
+//
+// The ids are the row numbers, and the parent id is empty for the roots.
+pub fn render_edges(roots: &[TreeNode]) -> String {
+    fn render_nodes(
+        nodes: &[TreeNode],
+        parent_id: Option<usize>,
+        depth: usize,
+        next_id: &mut usize,
+        output: &mut String,
+    ) {
+        for node in nodes {
+            let id = *next_id;
+            *next_id += 1;
+            let parent_id = parent_id.map_or(String::new(), |id| id.to_string());
+            output.push_str(&format!("{},{},{},{}\n", id, parent_id, depth, label(node)));
+            render_nodes(&node.children, Some(id), depth + 1, next_id, output);
+        }
+    }
+
+    let mut output = "id,parent_id,depth,label\n".to_string();
+    render_nodes(roots, None, 0, &mut 0, &mut output);
+    output
+}
+
+// Render the forest as one row per leaf, with the labels from the root to the leaf in
+// consecutive columns, e.g.
+// level1,level2,level3
+// Root,Child 1,Grandchild
+// Root,Child 2,
+pub fn render_paths(roots: &[TreeNode]) -> String {
+    fn collect_paths(nodes: &[TreeNode], path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+        for node in nodes {
+            path.push(label(node));
+            if node.children.is_empty() {
+                paths.push(path.clone());
+            } else {
+                collect_paths(&node.children, path, paths);
+            }
+            path.pop();
+        }
+    }
+
+    let mut paths = vec![];
+    collect_paths(roots, &mut vec![], &mut paths);
+    let levels = paths.iter().map(|path| path.len()).max().unwrap_or(0);
+
+    let header: Vec<String> = (1..=levels)
+        .map(|level| format!("level{}", level))
+        .collect();
+    let mut output = format!("{}\n", header.join(","));
+    for mut path in paths {
+        path.resize(levels, String::new());
+        output.push_str(&format!("{}\n", path.join(",")));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::new(
+                "Root, 1",
+                vec![
+                    TreeNode::new("Child", vec![TreeNode::from_label("Grandchild")]),
+                    TreeNode::from_label("Child \"2\"\\nQuota: 100"),
+                ],
+            ),
+            TreeNode::from_label("Root 2"),
+        ]
+    }
+
+    #[test]
+    fn test_field() {
+        assert_eq!(field("Root"), "Root");
+        assert_eq!(field("a,b"), "\"a,b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn test_render_edges() {
+        assert_eq!(
+            render_edges(&tree()),
+            [
+                "id,parent_id,depth,label",
+                "0,,0,\"Root, 1\"",
+                "1,0,1,Child",
+                "2,1,2,Grandchild",
+                "3,0,1,\"Child \"\"2\"\"",
+                "Quota: 100\"",
+                "4,,0,Root 2",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_render_paths() {
+        assert_eq!(
+            render_paths(&tree()),
+            [
+                "level1,level2,level3",
+                "\"Root, 1\",Child,Grandchild",
+                "\"Root, 1\",\"Child \"\"2\"\"",
+                "Quota: 100\",",
+                "Root 2,,",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+}
@@ -0,0 +1,117 @@
+use crate::tree::style::Style;
+use crate::tree::tree_node::TreeNode;
+use crate::writer::Direction;
+
+fn rankdir(direction: Direction) -> &'static str {
+    match direction {
+        Direction::TopToBottom => "TB",
+        Direction::LeftToRight => "LR",
+    }
+}
+
+// Graphviz node attributes that resemble the box style of the vertical tree
+fn node_attributes(style: Style) -> &'static str {
+    match style {
+        Style::Thin | Style::Ascii => "shape=box",
+        Style::Thick => "shape=box, penwidth=2",
+        Style::Double => "shape=box, peripheries=2",
+        Style::Chest => "shape=box, peripheries=2, penwidth=2",
+        Style::Balloon | Style::Balloon2 => "shape=box, style=rounded",
+    }
+}
+
+// Quote a label as a DOT string, with each line of a multi-line label separated by
+// '\n', which Graphviz renders as centered lines.
+fn quote_label(node: &TreeNode) -> String {
+    let lines: Vec<String> = node
+        .label_lines()
+        .iter()
+        .map(|line| line.replace('\\', "\\\\").replace('"', "\\\""))
+        .collect();
+    format!("\"{}\"", lines.join("\\n"))
+}
+
+// Render the forest as a Graphviz digraph, e.g.
+// digraph tree {
+//     rankdir=TB;
+//     node [shape=box];
+//     n0 [label="Root"];
+//     n0_0 [label="Child"];
+//     n0 -> n0_0;
+// }
+//
+// Node IDs are derived from the position of the node in the tree, e.g. 'n0_1' is the
+// second child of the first root, so that they stay the same as long as the structure
+// of the tree does not change.
+pub fn render(roots: &[TreeNode], direction: Direction, style: Style) -> String {
+    fn render_children(children: &[TreeNode], parent_id: &str, output: &mut String) {
+        for (idx, child) in children.iter().enumerate() {
+            let id = format!("{}_{}", parent_id, idx);
+            output.push_str(&format!("    {} [label={}];\n", id, quote_label(child)));
+            output.push_str(&format!("    {} -> {};\n", parent_id, id));
+            render_children(&child.children, &id, output);
+        }
+    }
+
+    let mut output = String::new();
+    output.push_str("digraph tree {\n");
+    output.push_str(&format!("    rankdir={};\n", rankdir(direction)));
+    output.push_str(&format!("    node [{}];\n", node_attributes(style)));
+    for (idx, root) in roots.iter().enumerate() {
+        let id = format!("n{}", idx);
+        output.push_str(&format!("    {} [label={}];\n", id, quote_label(root)));
+        render_children(&root.children, &id, &mut output);
+    }
+    output.push_str("}\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_render() {
+        let roots = vec![
+            TreeNode::new(
+                "Root 1",
+                vec![
+                    TreeNode::new("Child 1", vec![TreeNode::from_label("Grandchild")]),
+                    TreeNode::from_label("Child 2\\nQuota: \"100\""),
+                ],
+            ),
+            TreeNode::from_label("Root 2"),
+        ];
+
+        assert_canonical_eq(
+            r#"
+            digraph tree {
+                rankdir=TB;
+                node [shape=box];
+                n0 [label="Root 1"];
+                n0_0 [label="Child 1"];
+                n0 -> n0_0;
+                n0_0_0 [label="Grandchild"];
+                n0_0 -> n0_0_0;
+                n0_1 [label="Child 2\nQuota: \"100\""];
+                n0 -> n0_1;
+                n1 [label="Root 2"];
+            }
+            "#,
+            &render(&roots, Direction::TopToBottom, Style::Thin),
+        )
+    }
+
+    #[test]
+    fn test_render_left_to_right() {
+        let output = render(
+            &[TreeNode::from_label("Root")],
+            Direction::LeftToRight,
+            Style::Balloon,
+        );
+
+        assert!(output.contains("rankdir=LR;"));
+        assert!(output.contains("node [shape=box, style=rounded];"));
+    }
+}
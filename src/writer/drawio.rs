@@ -0,0 +1,189 @@
+use crate::tree::style::Style;
+use crate::tree::vertical::{Layout, NodeLayout};
+use crate::writer::escape_xml;
+
+// The size of a character of the vertical tree, in pixels
+const CELL_WIDTH: usize = 8;
+const CELL_HEIGHT: usize = 16;
+
+// The horizontal gap between trees, in characters
+const TREE_GAP: usize = 4;
+
+const EDGE_STYLE: &str = "edgeStyle=orthogonalEdgeStyle;rounded=0;endArrow=none;";
+
+// The edges leave the parent at the middle of the side facing the child, i.e. the
+// bottom side unless the tree is drawn bottom up
+fn edge_style(parent: &NodeLayout, child: &NodeLayout) -> String {
+    let (exit_y, entry_y) = if child.rect.y < parent.rect.y {
+        (0, 1)
+    } else {
+        (1, 0)
+    };
+    format!(
+        "{}exitX=0.5;exitY={};entryX=0.5;entryY={};",
+        EDGE_STYLE, exit_y, entry_y
+    )
+}
+
+// The draw.io style of the boxes, resembling the box style of the vertical tree
+fn vertex_style(style: Style) -> &'static str {
+    match style {
+        Style::Thin | Style::Ascii => "rounded=0;whiteSpace=wrap;",
+        Style::Thick => "rounded=0;whiteSpace=wrap;strokeWidth=2;",
+        Style::Double => "shape=ext;double=1;rounded=0;whiteSpace=wrap;",
+        Style::Chest => "shape=ext;double=1;rounded=0;whiteSpace=wrap;strokeWidth=2;",
+        Style::Balloon | Style::Balloon2 => "rounded=1;whiteSpace=wrap;",
+    }
+}
+
+// Render the vertical layout of each tree as a draw.io (diagrams.net) file, with one
+// cell per node and one edge from each parent to each of its children, e.g.
+// <mxCell id="n0" value="Root" style="..." vertex="1" parent="1">
+//   <mxGeometry x="0" y="0" width="64" height="48" as="geometry"/>
+// </mxCell>
+//
+// The positions are the coordinates of the vertical tree scaled to pixels, and the
+// trees of a forest are placed side by side.
+pub fn render(layouts: &[Layout], style: Style) -> String {
+    let mut cells = String::new();
+    let mut offset_x = 0;
+    // A layout holds several roots when the trees of a forest are side by side
+    let mut root_count = 0;
+    for layout in layouts {
+        // Path based ids, e.g. 'n0_1' for the second child of the first root
+        let mut ids: Vec<String> = Vec::with_capacity(layout.nodes.len());
+        let mut child_counts: Vec<usize> = vec![0; layout.nodes.len()];
+
+        for node in &layout.nodes {
+            let id = match node.parent {
+                None => {
+                    root_count += 1;
+                    format!("n{}", root_count - 1)
+                }
+                Some(parent) => {
+                    child_counts[parent] += 1;
+                    format!("{}_{}", ids[parent], child_counts[parent] - 1)
+                }
+            };
+
+            let value: Vec<String> = node.labels.iter().map(|line| escape_xml(line)).collect();
+            cells.push_str(&format!(
+                "        <mxCell id=\"{}\" value=\"{}\" style=\"{}\" vertex=\"1\" parent=\"1\">\n",
+                id,
+                value.join("&#xa;"),
+                vertex_style(style)
+            ));
+            cells.push_str(&format!(
+                "          <mxGeometry x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" as=\"geometry\"/>\n",
+                (offset_x + node.rect.x) * CELL_WIDTH,
+                node.rect.y * CELL_HEIGHT,
+                node.rect.width * CELL_WIDTH,
+                node.rect.height * CELL_HEIGHT
+            ));
+            cells.push_str("        </mxCell>\n");
+
+            if let Some(parent) = node.parent {
+                cells.push_str(&format!(
+                    "        <mxCell id=\"e{}\" style=\"{}\" edge=\"1\" parent=\"1\" source=\"{}\" target=\"{}\">\n",
+                    id,
+                    edge_style(&layout.nodes[parent], node),
+                    ids[parent],
+                    id
+                ));
+                cells.push_str("          <mxGeometry relative=\"1\" as=\"geometry\"/>\n");
+                cells.push_str("        </mxCell>\n");
+            }
+            ids.push(id);
+        }
+        offset_x += layout.width + TREE_GAP;
+    }
+
+    let mut output = String::new();
+    output.push_str("<mxfile host=\"astree\">\n");
+    output.push_str("  <diagram id=\"astree\" name=\"Tree\">\n");
+    output.push_str("    <mxGraphModel grid=\"1\" gridSize=\"8\">\n");
+    output.push_str("      <root>\n");
+    output.push_str("        <mxCell id=\"0\"/>\n");
+    output.push_str("        <mxCell id=\"1\" parent=\"0\"/>\n");
+    output.push_str(&cells);
+    output.push_str("      </root>\n");
+    output.push_str("    </mxGraphModel>\n");
+    output.push_str("  </diagram>\n");
+    output.push_str("</mxfile>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+    use crate::tree::tree_node::TreeNode;
+    use crate::tree::vertical;
+    use crate::tree::vertical::{LayoutOptions, Orientation};
+
+    #[test]
+    fn test_render() {
+        let root = TreeNode::new(
+            "Root",
+            vec![TreeNode::from_label("A & B"), TreeNode::from_label("C\\nD")],
+        );
+
+        assert_canonical_eq(
+            r#"
+            <mxfile host="astree">
+              <diagram id="astree" name="Tree">
+                <mxGraphModel grid="1" gridSize="8">
+                  <root>
+                    <mxCell id="0"/>
+                    <mxCell id="1" parent="0"/>
+                    <mxCell id="n0" value="Root" style="rounded=1;whiteSpace=wrap;" vertex="1" parent="1">
+                      <mxGeometry x="40" y="0" width="64" height="48" as="geometry"/>
+                    </mxCell>
+                    <mxCell id="n0_0" value="A &amp; B" style="rounded=1;whiteSpace=wrap;" vertex="1" parent="1">
+                      <mxGeometry x="0" y="64" width="72" height="48" as="geometry"/>
+                    </mxCell>
+                    <mxCell id="en0_0" style="edgeStyle=orthogonalEdgeStyle;rounded=0;endArrow=none;exitX=0.5;exitY=1;entryX=0.5;entryY=0;" edge="1" parent="1" source="n0" target="n0_0">
+                      <mxGeometry relative="1" as="geometry"/>
+                    </mxCell>
+                    <mxCell id="n0_1" value="C&#xa;D" style="rounded=1;whiteSpace=wrap;" vertex="1" parent="1">
+                      <mxGeometry x="88" y="64" width="40" height="64" as="geometry"/>
+                    </mxCell>
+                    <mxCell id="en0_1" style="edgeStyle=orthogonalEdgeStyle;rounded=0;endArrow=none;exitX=0.5;exitY=1;entryX=0.5;entryY=0;" edge="1" parent="1" source="n0" target="n0_1">
+                      <mxGeometry relative="1" as="geometry"/>
+                    </mxCell>
+                  </root>
+                </mxGraphModel>
+              </diagram>
+            </mxfile>
+            "#,
+            &render(
+                &[vertical::layout(
+                    &root,
+                    &LayoutOptions::default(),
+                    Orientation::TopDown,
+                )],
+                Style::Balloon,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_render_side_by_side() {
+        let roots = vec![
+            TreeNode::new("Root 1", vec![TreeNode::from_label("A")]),
+            TreeNode::new("Root 2", vec![TreeNode::from_label("B")]),
+        ];
+        let layout =
+            vertical::layout_forest(&roots, &LayoutOptions::default(), Orientation::TopDown, 4);
+        let result = render(&[layout], Style::Thin);
+        let ids: Vec<&str> = result
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("<mxCell id=\""))
+            .map(|line| &line[..line.find('"').unwrap()])
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["0", "1", "n0", "n0_0", "en0_0", "n1", "n1_0", "en1_0"]
+        );
+    }
+}
@@ -0,0 +1,358 @@
+// A small monospace bitmap font for rasterizing the character grid.
+//
+// Printable ASCII characters are 5 pixels wide and 9 pixels tall, where the first 7 rows
+// are above the baseline and the last 2 rows are for descenders. Each row is written as
+// a string of '#' (on) and '.' (off), with rows separated by '|'. Missing rows are off.
+//
+// Box-drawing characters are not part of the bitmap font. Instead, they are described by
+// the lines going out of the center of the cell, so that they can be drawn to fill any
+// cell size and connect seamlessly with their neighbours.
+
+pub const GLYPH_WIDTH: usize = 5;
+pub const GLYPH_HEIGHT: usize = 9;
+
+const GLYPHS: [(char, &str); 96] = [
+    (' ', ""),
+    ('!', "..#..|..#..|..#..|..#..|..#..|.....|..#.."),
+    ('"', ".#.#.|.#.#.|.#.#."),
+    ('#', ".#.#.|.#.#.|#####|.#.#.|#####|.#.#.|.#.#."),
+    ('$', "..#..|.####|#.#..|.###.|..#.#|####.|..#.."),
+    ('%', "##...|##..#|...#.|..#..|.#...|#..##|...##"),
+    ('&', ".##..|#..#.|#.#..|.#...|#.#.#|#..#.|.##.#"),
+    ('\'', "..#..|..#..|.#..."),
+    ('(', "...#.|..#..|.#...|.#...|.#...|..#..|...#."),
+    (')', ".#...|..#..|...#.|...#.|...#.|..#..|.#..."),
+    ('*', ".....|..#..|#.#.#|.###.|#.#.#|..#.."),
+    ('+', ".....|..#..|..#..|#####|..#..|..#.."),
+    (',', ".....|.....|.....|.....|.....|..#..|..#..|.#..."),
+    ('-', ".....|.....|.....|#####"),
+    ('.', ".....|.....|.....|.....|.....|.##..|.##.."),
+    ('/', ".....|....#|...#.|..#..|.#...|#...."),
+    ('0', ".###.|#...#|#..##|#.#.#|##..#|#...#|.###."),
+    ('1', "..#..|.##..|..#..|..#..|..#..|..#..|.###."),
+    ('2', ".###.|#...#|....#|...#.|..#..|.#...|#####"),
+    ('3', "#####|...#.|..#..|...#.|....#|#...#|.###."),
+    ('4', "...#.|..##.|.#.#.|#..#.|#####|...#.|...#."),
+    ('5', "#####|#....|####.|....#|....#|#...#|.###."),
+    ('6', "..##.|.#...|#....|####.|#...#|#...#|.###."),
+    ('7', "#####|....#|...#.|..#..|.#...|.#...|.#..."),
+    ('8', ".###.|#...#|#...#|.###.|#...#|#...#|.###."),
+    ('9', ".###.|#...#|#...#|.####|....#|...#.|.##.."),
+    (':', ".....|.##..|.##..|.....|.##..|.##.."),
+    (';', ".....|.##..|.##..|.....|.##..|..#..|.#..."),
+    ('<', "...#.|..#..|.#...|#....|.#...|..#..|...#."),
+    ('=', ".....|.....|#####|.....|#####"),
+    ('>', ".#...|..#..|...#.|....#|...#.|..#..|.#..."),
+    ('?', ".###.|#...#|....#|...#.|..#..|.....|..#.."),
+    ('@', ".###.|#...#|....#|.##.#|#.#.#|#.#.#|.###."),
+    ('A', ".###.|#...#|#...#|#####|#...#|#...#|#...#"),
+    ('B', "####.|#...#|#...#|####.|#...#|#...#|####."),
+    ('C', ".###.|#...#|#....|#....|#....|#...#|.###."),
+    ('D', "###..|#..#.|#...#|#...#|#...#|#..#.|###.."),
+    ('E', "#####|#....|#....|####.|#....|#....|#####"),
+    ('F', "#####|#....|#....|####.|#....|#....|#...."),
+    ('G', ".###.|#...#|#....|#.###|#...#|#...#|.####"),
+    ('H', "#...#|#...#|#...#|#####|#...#|#...#|#...#"),
+    ('I', ".###.|..#..|..#..|..#..|..#..|..#..|.###."),
+    ('J', "..###|...#.|...#.|...#.|...#.|#..#.|.##.."),
+    ('K', "#...#|#..#.|#.#..|##...|#.#..|#..#.|#...#"),
+    ('L', "#....|#....|#....|#....|#....|#....|#####"),
+    ('M', "#...#|##.##|#.#.#|#.#.#|#...#|#...#|#...#"),
+    ('N', "#...#|#...#|##..#|#.#.#|#..##|#...#|#...#"),
+    ('O', ".###.|#...#|#...#|#...#|#...#|#...#|.###."),
+    ('P', "####.|#...#|#...#|####.|#....|#....|#...."),
+    ('Q', ".###.|#...#|#...#|#...#|#.#.#|#..#.|.##.#"),
+    ('R', "####.|#...#|#...#|####.|#.#..|#..#.|#...#"),
+    ('S', ".####|#....|#....|.###.|....#|....#|####."),
+    ('T', "#####|..#..|..#..|..#..|..#..|..#..|..#.."),
+    ('U', "#...#|#...#|#...#|#...#|#...#|#...#|.###."),
+    ('V', "#...#|#...#|#...#|#...#|#...#|.#.#.|..#.."),
+    ('W', "#...#|#...#|#...#|#.#.#|#.#.#|#.#.#|.#.#."),
+    ('X', "#...#|#...#|.#.#.|..#..|.#.#.|#...#|#...#"),
+    ('Y', "#...#|#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "#####|....#|...#.|..#..|.#...|#....|#####"),
+    ('[', ".###.|.#...|.#...|.#...|.#...|.#...|.###."),
+    ('\\', ".....|#....|.#...|..#..|...#.|....#"),
+    (']', ".###.|...#.|...#.|...#.|...#.|...#.|.###."),
+    ('^', "..#..|.#.#.|#...#"),
+    ('_', ".....|.....|.....|.....|.....|.....|#####"),
+    ('`', ".#...|..#..|...#."),
+    ('a', ".....|.....|.###.|....#|.####|#...#|.####"),
+    ('b', "#....|#....|#.##.|##..#|#...#|#...#|####."),
+    ('c', ".....|.....|.###.|#....|#....|#...#|.###."),
+    ('d', "....#|....#|.##.#|#..##|#...#|#...#|.####"),
+    ('e', ".....|.....|.###.|#...#|#####|#....|.###."),
+    ('f', "..##.|.#..#|.#...|###..|.#...|.#...|.#..."),
+    ('g', ".....|.....|.####|#...#|#...#|#...#|.####|....#|.###."),
+    ('h', "#....|#....|#.##.|##..#|#...#|#...#|#...#"),
+    ('i', "..#..|.....|.##..|..#..|..#..|..#..|.###."),
+    ('j', "...#.|.....|..##.|...#.|...#.|...#.|...#.|#..#.|.##.."),
+    ('k', "#....|#....|#..#.|#.#..|##...|#.#..|#..#."),
+    ('l', ".##..|..#..|..#..|..#..|..#..|..#..|.###."),
+    ('m', ".....|.....|##.#.|#.#.#|#.#.#|#...#|#...#"),
+    ('n', ".....|.....|#.##.|##..#|#...#|#...#|#...#"),
+    ('o', ".....|.....|.###.|#...#|#...#|#...#|.###."),
+    ('p', ".....|.....|####.|#...#|#...#|#...#|####.|#....|#...."),
+    ('q', ".....|.....|.####|#...#|#...#|#...#|.####|....#|....#"),
+    ('r', ".....|.....|#.##.|##..#|#....|#....|#...."),
+    ('s', ".....|.....|.####|#....|.###.|....#|####."),
+    ('t', ".#...|.#...|###..|.#...|.#...|.#..#|..##."),
+    ('u', ".....|.....|#...#|#...#|#...#|#..##|.##.#"),
+    ('v', ".....|.....|#...#|#...#|#...#|.#.#.|..#.."),
+    ('w', ".....|.....|#...#|#...#|#.#.#|#.#.#|.#.#."),
+    ('x', ".....|.....|#...#|.#.#.|..#..|.#.#.|#...#"),
+    ('y', ".....|.....|#...#|#...#|#...#|#...#|.####|....#|.###."),
+    ('z', ".....|.....|#####|...#.|..#..|.#...|#####"),
+    ('{', "...#.|..#..|..#..|.#...|..#..|..#..|...#."),
+    ('|', "..#..|..#..|..#..|..#..|..#..|..#..|..#.."),
+    ('}', ".#...|..#..|..#..|...#.|..#..|..#..|.#..."),
+    ('~', ".....|.....|.#...|#.#.#|...#."),
+    // Used as the connection of the balloon2 style
+    ('¤', ".....|#...#|.###.|.#.#.|.###.|#...#"),
+];
+
+// Whether the pixel at (x, y) of the glyph is on, or None if there is no glyph for
+// the character.
+pub fn glyph(ch: char) -> Option<[[bool; GLYPH_WIDTH]; GLYPH_HEIGHT]> {
+    let (_, pattern) = GLYPHS.iter().find(|(glyph_ch, _)| *glyph_ch == ch)?;
+    let mut pixels = [[false; GLYPH_WIDTH]; GLYPH_HEIGHT];
+    if !pattern.is_empty() {
+        for (y, row) in pattern.split('|').enumerate() {
+            for (x, pixel) in row.chars().enumerate() {
+                pixels[y][x] = pixel == '#';
+            }
+        }
+    }
+    Some(pixels)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weight {
+    Light,
+    Heavy,
+    Double,
+}
+
+// The lines going out of the center of the cell for a box-drawing character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arms {
+    pub up: Option<Weight>,
+    pub down: Option<Weight>,
+    pub left: Option<Weight>,
+    pub right: Option<Weight>,
+}
+
+// A filled rectangle of a box-drawing character, relative to the top left corner of the
+// cell, with the ends excluded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bar {
+    pub x_start: usize,
+    pub x_end: usize,
+    pub y_start: usize,
+    pub y_end: usize,
+}
+
+impl Arms {
+    // The bars that draw the lines from the center of a cell of the given size to its
+    // edges, so that the lines of neighbouring cells are connected.
+    pub fn bars(&self, cell_width: usize, cell_height: usize) -> Vec<Bar> {
+        let thin = (cell_width / 8).max(1);
+        let center_x = cell_width / 2;
+        let center_y = cell_height / 2;
+
+        // The distance from the center to each of the two lines of a double line
+        let offset = thin * 3 / 2 + 1;
+
+        let mut bars = vec![];
+
+        for (weight, is_vertical, towards_start) in [
+            (self.up, true, true),
+            (self.down, true, false),
+            (self.left, false, true),
+            (self.right, false, false),
+        ] {
+            let weight = match weight {
+                Some(weight) => weight,
+                None => continue,
+            };
+            // Lines are described along the arm (main axis) and across it (cross axis)
+            let (center_main, center_cross) = if is_vertical {
+                (center_y, center_x)
+            } else {
+                (center_x, center_y)
+            };
+            let (main_start, main_end) = if is_vertical {
+                (0, cell_height)
+            } else {
+                (0, cell_width)
+            };
+            // The perpendicular arms, on either side of this arm
+            let (before, after) = if is_vertical {
+                (self.left, self.right)
+            } else {
+                (self.up, self.down)
+            };
+
+            let mut lines: Vec<(usize, usize, usize, usize)> = vec![];
+            match weight {
+                Weight::Light | Weight::Heavy => {
+                    let thickness = if weight == Weight::Light {
+                        thin
+                    } else {
+                        thin * 2
+                    };
+                    let cross_start = center_cross - thickness / 2;
+                    let cross_end = cross_start + thickness;
+                    // Cover the center, so that the arms are joined
+                    let (from, to) = if towards_start {
+                        (main_start, center_main - thickness / 2 + thickness)
+                    } else {
+                        (center_main - thickness / 2, main_end)
+                    };
+                    lines.push((from, to, cross_start, cross_end));
+                }
+                Weight::Double => {
+                    for (cross_start, has_perpendicular) in [
+                        (center_cross - offset, before.is_some()),
+                        (center_cross + offset, after.is_some()),
+                    ] {
+                        // Stop at the inner line when there is a perpendicular arm on
+                        // the same side, and at the outer line otherwise.
+                        let (from, to) = if towards_start {
+                            let to = if has_perpendicular {
+                                center_main - offset + thin
+                            } else {
+                                center_main + offset + thin
+                            };
+                            (main_start, to)
+                        } else {
+                            let from = if has_perpendicular {
+                                center_main + offset
+                            } else {
+                                center_main - offset
+                            };
+                            (from, main_end)
+                        };
+                        lines.push((from, to, cross_start, cross_start + thin));
+                    }
+                }
+            }
+
+            for (from, to, cross_start, cross_end) in lines {
+                bars.push(if is_vertical {
+                    Bar {
+                        x_start: cross_start,
+                        x_end: cross_end,
+                        y_start: from,
+                        y_end: to,
+                    }
+                } else {
+                    Bar {
+                        x_start: from,
+                        x_end: to,
+                        y_start: cross_start,
+                        y_end: cross_end,
+                    }
+                });
+            }
+        }
+        bars
+    }
+}
+
+pub fn arms(ch: char) -> Option<Arms> {
+    // (up, down, left, right)
+    let (up, down, left, right, weight) = match ch {
+        '─' => (false, false, true, true, Weight::Light),
+        '│' => (true, true, false, false, Weight::Light),
+        '┌' | '╭' => (false, true, false, true, Weight::Light),
+        '┐' | '╮' => (false, true, true, false, Weight::Light),
+        '└' | '╰' => (true, false, false, true, Weight::Light),
+        '┘' | '╯' => (true, false, true, false, Weight::Light),
+        '├' => (true, true, false, true, Weight::Light),
+        '┤' => (true, true, true, false, Weight::Light),
+        '┬' => (false, true, true, true, Weight::Light),
+        '┴' => (true, false, true, true, Weight::Light),
+        '┼' => (true, true, true, true, Weight::Light),
+        '━' => (false, false, true, true, Weight::Heavy),
+        '┃' => (true, true, false, false, Weight::Heavy),
+        '┏' => (false, true, false, true, Weight::Heavy),
+        '┓' => (false, true, true, false, Weight::Heavy),
+        '┗' => (true, false, false, true, Weight::Heavy),
+        '┛' => (true, false, true, false, Weight::Heavy),
+        '┣' => (true, true, false, true, Weight::Heavy),
+        '┫' => (true, true, true, false, Weight::Heavy),
+        '┳' => (false, true, true, true, Weight::Heavy),
+        '┻' => (true, false, true, true, Weight::Heavy),
+        '╋' => (true, true, true, true, Weight::Heavy),
+        '═' => (false, false, true, true, Weight::Double),
+        '║' => (true, true, false, false, Weight::Double),
+        '╔' => (false, true, false, true, Weight::Double),
+        '╗' => (false, true, true, false, Weight::Double),
+        '╚' => (true, false, false, true, Weight::Double),
+        '╝' => (true, false, true, false, Weight::Double),
+        '╠' => (true, true, false, true, Weight::Double),
+        '╣' => (true, true, true, false, Weight::Double),
+        '╦' => (false, true, true, true, Weight::Double),
+        '╩' => (true, false, true, true, Weight::Double),
+        '╬' => (true, true, true, true, Weight::Double),
+        _ => return None,
+    };
+    let arm = |present: bool| if present { Some(weight) } else { None };
+    Some(Arms {
+        up: arm(up),
+        down: arm(down),
+        left: arm(left),
+        right: arm(right),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyphs_are_well_formed() {
+        for (ch, pattern) in GLYPHS.iter() {
+            if pattern.is_empty() {
+                continue;
+            }
+            let rows: Vec<&str> = pattern.split('|').collect();
+            assert!(rows.len() <= GLYPH_HEIGHT, "too many rows for {:?}", ch);
+            for row in rows {
+                assert_eq!(row.len(), GLYPH_WIDTH, "invalid row {:?} for {:?}", row, ch);
+                assert!(row.chars().all(|pixel| pixel == '#' || pixel == '.'));
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_printable_ascii_characters_have_glyphs() {
+        for ch in ' '..='~' {
+            assert!(glyph(ch).is_some(), "missing glyph for {:?}", ch);
+        }
+    }
+
+    #[test]
+    fn test_glyph() {
+        let pixels = glyph('T').unwrap();
+        assert_eq!(pixels[0], [true; 5]);
+        assert_eq!(pixels[1], [false, false, true, false, false]);
+        assert_eq!(pixels[8], [false; 5]);
+        assert!(glyph('€').is_none());
+    }
+
+    #[test]
+    fn test_arms() {
+        assert_eq!(
+            arms('┬'),
+            Some(Arms {
+                up: None,
+                down: Some(Weight::Light),
+                left: Some(Weight::Light),
+                right: Some(Weight::Light),
+            })
+        );
+        assert_eq!(arms('A'), None);
+    }
+}
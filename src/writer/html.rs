@@ -0,0 +1,172 @@
+use crate::tree::tree_node::TreeNode;
+use crate::writer::escape_xml;
+
+// Everything is inlined so that the page can be published as a single file, without
+// relying on any external stylesheets, scripts or CDNs.
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 1.5em; }
+.toolbar { margin-bottom: 1em; }
+.toolbar input { margin-left: 0.5em; padding: 0.2em 0.4em; }
+.tree, .tree ul { list-style: none; margin: 0; padding-left: 1.5em; }
+.tree { padding-left: 0; }
+.tree li { position: relative; line-height: 1.5em; }
+.tree ul > li::before { content: ""; position: absolute; left: -1em; top: 0; bottom: 0; border-left: 1px solid #999; }
+.tree ul > li:last-child::before { bottom: auto; height: 0.75em; }
+.tree ul > li::after { content: ""; position: absolute; left: -1em; top: 0.75em; width: 0.75em; border-top: 1px solid #999; }
+.tree summary { cursor: pointer; }
+.tree .leaf { display: block; padding-left: 1.1em; }
+.tree .match { background: #fff3a3; }
+"#;
+
+const SCRIPT: &str = r#"
+(function () {
+  var tree = document.getElementById('tree');
+
+  function setOpen(open) {
+    tree.querySelectorAll('details').forEach(function (details) { details.open = open; });
+  }
+  document.getElementById('expand-all').addEventListener('click', function () { setOpen(true); });
+  document.getElementById('collapse-all').addEventListener('click', function () { setOpen(false); });
+
+  // Show the items matching the query, together with their ancestors and descendants.
+  // Returns whether the item itself, or any of its descendants, matches the query.
+  function filter(item, query, ancestorMatched) {
+    var label = item.querySelector(':scope > details > summary, :scope > .leaf');
+    var matched = query !== '' && label.textContent.toLowerCase().indexOf(query) >= 0;
+    var descendantMatched = false;
+    item.querySelectorAll(':scope > details > ul > li').forEach(function (child) {
+      if (filter(child, query, ancestorMatched || matched)) { descendantMatched = true; }
+    });
+    var details = item.querySelector(':scope > details');
+    if (details && descendantMatched) { details.open = true; }
+    label.classList.toggle('match', matched);
+    item.hidden = !(query === '' || ancestorMatched || matched || descendantMatched);
+    return matched || descendantMatched;
+  }
+  document.getElementById('filter').addEventListener('input', function (event) {
+    var query = event.target.value.trim().toLowerCase();
+    tree.querySelectorAll(':scope > li').forEach(function (item) { filter(item, query, false); });
+  });
+})();
+"#;
+
+pub fn render(roots: &[TreeNode]) -> String {
+    let title = match roots {
+        [root] => root.label_lines()[0].clone(),
+        _ => "Tree".to_string(),
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_xml(&title)));
+    html.push_str(&format!("<style>{}</style>\n", STYLE));
+    html.push_str("</head>\n<body>\n");
+    html.push_str("<div class=\"toolbar\">\n");
+    html.push_str("  <button type=\"button\" id=\"expand-all\">Expand all</button>\n");
+    html.push_str("  <button type=\"button\" id=\"collapse-all\">Collapse all</button>\n");
+    html.push_str("  <input type=\"search\" id=\"filter\" placeholder=\"Filter\">\n");
+    html.push_str("</div>\n");
+    html.push_str("<ul class=\"tree\" id=\"tree\">\n");
+    render_items(roots, 0, &mut html);
+    html.push_str("</ul>\n");
+    html.push_str(&format!("<script>{}</script>\n", SCRIPT));
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// Each node with children becomes a <details> element, so that the subtree can be
+// expanded and collapsed by clicking on its <summary>.
+//
+// <li>
+//   <details open>
+//     <summary>Root</summary>
+//     <ul>
+//       <li><span class="leaf">Child</span></li>
+//     </ul>
+//   </details>
+// </li>
+fn render_items(nodes: &[TreeNode], depth: usize, html: &mut String) {
+    let indent = "      ".repeat(depth);
+    for node in nodes {
+        let label = node
+            .label_lines()
+            .iter()
+            .map(|line| escape_xml(line))
+            .collect::<Vec<String>>()
+            .join("<br>");
+
+        if node.children.is_empty() {
+            html.push_str(&format!(
+                "{}<li><span class=\"leaf\">{}</span></li>\n",
+                indent, label
+            ));
+        } else {
+            html.push_str(&format!("{}<li>\n", indent));
+            html.push_str(&format!("{}  <details open>\n", indent));
+            html.push_str(&format!("{}    <summary>{}</summary>\n", indent, label));
+            html.push_str(&format!("{}    <ul>\n", indent));
+            render_items(&node.children, depth + 1, html);
+            html.push_str(&format!("{}    </ul>\n", indent));
+            html.push_str(&format!("{}  </details>\n", indent));
+            html.push_str(&format!("{}</li>\n", indent));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_render_items() {
+        let mut html = String::new();
+        render_items(
+            &[TreeNode::new(
+                "Root",
+                vec![
+                    TreeNode::new("Child 1", vec![TreeNode::from_label("Grandchild")]),
+                    TreeNode::from_label("Child <2>\\nsecond line"),
+                ],
+            )],
+            0,
+            &mut html,
+        );
+
+        assert_canonical_eq(
+            r#"
+            <li>
+              <details open>
+                <summary>Root</summary>
+                <ul>
+                  <li>
+                    <details open>
+                      <summary>Child 1</summary>
+                      <ul>
+                        <li><span class="leaf">Grandchild</span></li>
+                      </ul>
+                    </details>
+                  </li>
+                  <li><span class="leaf">Child &lt;2&gt;<br>second line</span></li>
+                </ul>
+              </details>
+            </li>
+            "#,
+            &html,
+        )
+    }
+
+    #[test]
+    fn test_render_page() {
+        let html = render(&[TreeNode::from_label("Root & Co")]);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Root &amp; Co</title>"));
+        assert!(html.contains("id=\"expand-all\""));
+        assert!(html.contains("id=\"collapse-all\""));
+        assert!(html.contains("id=\"filter\""));
+        // Self-contained, no external resources
+        assert!(!html.contains("src="));
+        assert!(!html.contains("href="));
+    }
+}
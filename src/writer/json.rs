@@ -0,0 +1,165 @@
+use crate::tree::tree_node::TreeNode;
+
+// Quote and escape a string as a JSON string literal
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Multi-line labels are exported with actual line breaks
+fn label(node: &TreeNode) -> String {
+    quote(&node.label_lines().join("\n"))
+}
+
+// The whole forest as a single JSON array, e.g.
+// [
+//   {
+//     "label": "Root",
+//     "children": [
+//       {
+//         "label": "Child",
+//         "children": []
+//       }
+//     ]
+//   }
+// ]
+pub fn render(roots: &[TreeNode]) -> String {
+    fn render_array(nodes: &[TreeNode], indent: &str, output: &mut String) {
+        if nodes.is_empty() {
+            output.push_str("[]");
+            return;
+        }
+
+        output.push_str("[\n");
+        for (idx, node) in nodes.iter().enumerate() {
+            output.push_str(&format!("{}  {{\n", indent));
+            output.push_str(&format!("{}    \"label\": {},\n", indent, label(node)));
+            output.push_str(&format!("{}    \"children\": ", indent));
+            render_array(&node.children, &format!("{}    ", indent), output);
+            output.push('\n');
+            if idx < nodes.len() - 1 {
+                output.push_str(&format!("{}  }},\n", indent));
+            } else {
+                output.push_str(&format!("{}  }}\n", indent));
+            }
+        }
+        output.push_str(&format!("{}]", indent));
+    }
+
+    let mut output = String::new();
+    render_array(roots, "", &mut output);
+    output.push('\n');
+    output
+}
+
+// JSON Lines, with one object per node in depth-first order. Each node comes with
+// the labels of all its ancestors (and itself) as the path, and its depth, where
+// root nodes are at depth 0.
+pub fn render_lines(roots: &[TreeNode]) -> String {
+    fn render_lines_internal(nodes: &[TreeNode], path: &mut Vec<String>, output: &mut String) {
+        for node in nodes {
+            path.push(label(node));
+            output.push_str(&format!(
+                "{{\"path\":[{}],\"depth\":{},\"label\":{}}}\n",
+                path.join(","),
+                path.len() - 1,
+                label(node)
+            ));
+            render_lines_internal(&node.children, path, output);
+            path.pop();
+        }
+    }
+
+    let mut output = String::new();
+    render_lines_internal(roots, &mut vec![], &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("Root"), r#""Root""#);
+        assert_eq!(quote("a \"b\"\\c\nd\u{1}"), r#""a \"b\"\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn test_render() {
+        let roots = vec![
+            TreeNode::new(
+                "Root 1",
+                vec![
+                    TreeNode::from_label("Child 1\\nQuota: 100"),
+                    TreeNode::from_label("Child 2"),
+                ],
+            ),
+            TreeNode::from_label("Root 2"),
+        ];
+
+        assert_canonical_eq(
+            r#"
+            [
+              {
+                "label": "Root 1",
+                "children": [
+                  {
+                    "label": "Child 1\nQuota: 100",
+                    "children": []
+                  },
+                  {
+                    "label": "Child 2",
+                    "children": []
+                  }
+                ]
+              },
+              {
+                "label": "Root 2",
+                "children": []
+              }
+            ]
+            "#,
+            &render(&roots),
+        )
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(render(&[]), "[]\n");
+    }
+
+    #[test]
+    fn test_render_lines() {
+        let roots = vec![TreeNode::new(
+            "Root",
+            vec![TreeNode::new(
+                "Child",
+                vec![TreeNode::from_label("Grandchild")],
+            )],
+        )];
+
+        assert_canonical_eq(
+            r#"
+            {"path":["Root"],"depth":0,"label":"Root"}
+            {"path":["Root","Child"],"depth":1,"label":"Child"}
+            {"path":["Root","Child","Grandchild"],"depth":2,"label":"Grandchild"}
+            "#,
+            &render_lines(&roots),
+        )
+    }
+}
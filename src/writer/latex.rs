@@ -0,0 +1,151 @@
+use crate::tree::style::Style;
+use crate::tree::tree_node::TreeNode;
+use crate::writer::Direction;
+
+// Escape the characters that are special in LaTeX
+fn escape_line(line: &str) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    for ch in line.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '{' | '}' | '#' | '$' | '%' | '&' | '_' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+// The label is always wrapped in braces, so that characters such as '[', ']' and ','
+// are not interpreted by the bracket notation of forest. Multi-line labels are
+// separated by '\\', which requires 'align=center'.
+fn label(node: &TreeNode) -> String {
+    let lines: Vec<String> = node
+        .label_lines()
+        .iter()
+        .map(|line| escape_line(line))
+        .collect();
+    format!("{{{}}}", lines.join("\\\\"))
+}
+
+// TikZ options that resemble the box style of the vertical tree
+fn border_options(style: Style) -> &'static str {
+    match style {
+        Style::Thin | Style::Ascii => "draw",
+        Style::Thick => "draw, very thick",
+        Style::Double => "draw, double",
+        Style::Chest => "draw, double, thick",
+        Style::Balloon | Style::Balloon2 => "draw, rounded corners",
+    }
+}
+
+// Render each tree as a `forest` environment (from the LaTeX package of the same name), e.g.
+// \begin{forest}
+//   for tree={align=center}
+//   [{Root}
+//     [{Child}]
+//   ]
+// \end{forest}
+//
+// Nodes are drawn with borders matching the given style, or without borders if None.
+pub fn render(roots: &[TreeNode], direction: Direction, borders: Option<Style>) -> String {
+    fn render_node(node: &TreeNode, indent: &str, output: &mut String) {
+        if node.children.is_empty() {
+            output.push_str(&format!("{}[{}]\n", indent, label(node)));
+        } else {
+            output.push_str(&format!("{}[{}\n", indent, label(node)));
+            for child in &node.children {
+                render_node(child, &format!("{}  ", indent), output);
+            }
+            output.push_str(&format!("{}]\n", indent));
+        }
+    }
+
+    let mut options = vec!["align=center"];
+    if let Some(style) = borders {
+        options.push(border_options(style));
+    }
+    if direction == Direction::LeftToRight {
+        // Grow towards the right, with the first child at the top
+        options.push("grow'=0");
+    }
+
+    let mut output = "% Requires \\usepackage{forest}\n".to_string();
+    for root in roots {
+        output.push_str("\\begin{forest}\n");
+        output.push_str(&format!("  for tree={{{}}}\n", options.join(", ")));
+        render_node(root, "  ", &mut output);
+        output.push_str("\\end{forest}\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_escape_line() {
+        assert_eq!(escape_line("50% of $10 & #1"), "50\\% of \\$10 \\& \\#1");
+        assert_eq!(escape_line("a_b{c}"), "a\\_b\\{c\\}");
+        assert_eq!(
+            escape_line("~^\\"),
+            "\\textasciitilde{}\\textasciicircum{}\\textbackslash{}"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let roots = vec![TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new("Child [1]", vec![TreeNode::from_label("Grandchild")]),
+                TreeNode::from_label("Child 2\\nQuota: 100%"),
+            ],
+        )];
+
+        assert_canonical_eq(
+            r#"
+            % Requires \usepackage{forest}
+            \begin{forest}
+              for tree={align=center}
+              [{Root}
+                [{Child [1]}
+                  [{Grandchild}]
+                ]
+                [{Child 2\\Quota: 100\%}]
+              ]
+            \end{forest}
+            "#,
+            &render(&roots, Direction::TopToBottom, None),
+        )
+    }
+
+    #[test]
+    fn test_render_multiple_roots_with_borders() {
+        let roots = vec![
+            TreeNode::from_label("Root 1"),
+            TreeNode::from_label("Root 2"),
+        ];
+
+        assert_canonical_eq(
+            r#"
+            % Requires \usepackage{forest}
+            \begin{forest}
+              for tree={align=center, draw, rounded corners, grow'=0}
+              [{Root 1}]
+            \end{forest}
+            \begin{forest}
+              for tree={align=center, draw, rounded corners, grow'=0}
+              [{Root 2}]
+            \end{forest}
+            "#,
+            &render(&roots, Direction::LeftToRight, Some(Style::Balloon)),
+        )
+    }
+}
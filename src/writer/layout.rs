@@ -0,0 +1,127 @@
+use crate::tree::vertical::{Layout, Point2D};
+use crate::writer::json::quote;
+
+fn point(point: &Point2D<usize>) -> String {
+    format!("{{\"x\": {}, \"y\": {}}}", point.x, point.y)
+}
+
+// The geometry of each tree as JSON, with one node or connector per line, e.g.
+// [
+//   {
+//     "width": 10,
+//     "height": 6,
+//     "nodes": [
+//       {"id": 0, "parent": null, "depth": 0, "labels": ["root"], "rect": {...}, "center": {...}},
+//       {"id": 1, "parent": 0, "depth": 1, "labels": ["child1"], "rect": {...}, "center": {...}}
+//     ],
+//     "connectors": [
+//       {"from": {"x": 4, "y": 2}, "to": {"x": 4, "y": 3}}
+//     ]
+//   }
+// ]
+pub fn render(layouts: &[Layout]) -> String {
+    let mut output = String::new();
+    output.push('[');
+    for (layout_idx, layout) in layouts.iter().enumerate() {
+        if layout_idx > 0 {
+            output.push(',');
+        }
+        output.push_str("\n  {\n");
+        output.push_str(&format!("    \"width\": {},\n", layout.width));
+        output.push_str(&format!("    \"height\": {},\n", layout.height));
+
+        let nodes: Vec<String> = layout
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| {
+                format!(
+                    "{{\"id\": {}, \"parent\": {}, \"depth\": {}, \"labels\": [{}], \"rect\": {{\"x\": {}, \"y\": {}, \"width\": {}, \"height\": {}}}, \"center\": {}}}",
+                    idx,
+                    node.parent.map_or("null".to_string(), |parent| parent.to_string()),
+                    node.depth,
+                    node.labels.iter().map(|label| quote(label)).collect::<Vec<String>>().join(", "),
+                    node.rect.x,
+                    node.rect.y,
+                    node.rect.width,
+                    node.rect.height,
+                    point(&node.center),
+                )
+            })
+            .collect();
+        output.push_str(&format!("    \"nodes\": {},\n", array(&nodes)));
+
+        let connectors: Vec<String> = layout
+            .connectors
+            .iter()
+            .map(|segment| {
+                format!(
+                    "{{\"from\": {}, \"to\": {}}}",
+                    point(&segment.from),
+                    point(&segment.to)
+                )
+            })
+            .collect();
+        output.push_str(&format!("    \"connectors\": {}\n", array(&connectors)));
+        output.push_str("  }");
+    }
+    if !layouts.is_empty() {
+        output.push('\n');
+    }
+    output.push_str("]\n");
+    output
+}
+
+// An array nested in the layout object, with each element on its own line
+fn array(elements: &[String]) -> String {
+    if elements.is_empty() {
+        return "[]".to_string();
+    }
+
+    let lines: Vec<String> = elements
+        .iter()
+        .map(|element| format!("      {}", element))
+        .collect();
+    format!("[\n{}\n    ]", lines.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+    use crate::tree::tree_node::TreeNode;
+    use crate::tree::vertical::{layout, LayoutOptions, Orientation};
+
+    #[test]
+    fn test_render() {
+        let root = TreeNode::new("root", vec![TreeNode::from_label("child")]);
+
+        assert_canonical_eq(
+            r#"
+            [
+              {
+                "width": 9,
+                "height": 6,
+                "nodes": [
+                  {"id": 0, "parent": null, "depth": 0, "labels": ["root"], "rect": {"x": 1, "y": 0, "width": 8, "height": 3}, "center": {"x": 4, "y": 1}},
+                  {"id": 1, "parent": 0, "depth": 1, "labels": ["child"], "rect": {"x": 0, "y": 3, "width": 9, "height": 3}, "center": {"x": 4, "y": 4}}
+                ],
+                "connectors": [
+                  {"from": {"x": 4, "y": 2}, "to": {"x": 4, "y": 3}}
+                ]
+              }
+            ]
+            "#,
+            &render(&[layout(
+                &root,
+                &LayoutOptions::default(),
+                Orientation::TopDown,
+            )]),
+        )
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(render(&[]), "[]\n");
+    }
+}
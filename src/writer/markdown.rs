@@ -0,0 +1,194 @@
+use crate::tree::tree_node::TreeNode;
+use std::collections::HashMap;
+
+// Render the forest with headings, which is the format that astree reads, e.g.
+// # Root
+// ## Child
+// Quota: 100
+//
+// The first line of a multi-line label is used as the heading, and the remaining
+// lines are written as content lines under the heading.
+pub fn render_headings(roots: &[TreeNode]) -> String {
+    fn render_nodes(nodes: &[TreeNode], depth: usize, output: &mut String) {
+        for node in nodes {
+            let lines = node.label_lines();
+            output.push_str(&format!("{} {}\n", "#".repeat(depth), lines[0]));
+            for line in &lines[1..] {
+                output.push_str(&format!("{}\n", line));
+            }
+            render_nodes(&node.children, depth + 1, output);
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, 1, &mut output);
+    output
+}
+
+// Render the forest as a nested bullet list, e.g.
+// - Root
+//   - Child\
+//     Quota: 100
+//
+// Multi-line labels are kept together in the same list item, with hard line breaks.
+pub fn render_list(roots: &[TreeNode]) -> String {
+    fn render_nodes(nodes: &[TreeNode], indent: &str, output: &mut String) {
+        for node in nodes {
+            let lines = node.label_lines();
+            let continuation = format!("\\\n{}  ", indent);
+            output.push_str(&format!("{}- {}\n", indent, lines.join(&continuation)));
+            render_nodes(&node.children, &format!("{}  ", indent), output);
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, "", &mut output);
+    output
+}
+
+// Render the forest as a table of contents, linking to the headings written by
+// `render_headings` with GitHub-style anchors, e.g.
+// - [Root](#root)
+//   - [Child 1](#child-1)
+pub fn render_toc(roots: &[TreeNode]) -> String {
+    fn render_nodes(
+        nodes: &[TreeNode],
+        indent: &str,
+        anchors: &mut HashMap<String, usize>,
+        output: &mut String,
+    ) {
+        for node in nodes {
+            let heading = &node.label_lines()[0];
+            let text = heading.replace('[', "\\[").replace(']', "\\]");
+            let anchor = unique_anchor(heading, anchors);
+            output.push_str(&format!("{}- [{}](#{})\n", indent, text, anchor));
+            render_nodes(&node.children, &format!("{}  ", indent), anchors, output);
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, "", &mut HashMap::new(), &mut output);
+    output
+}
+
+// The anchor that GitHub generates for a heading: lower case, with spaces replaced by
+// hyphens, and any punctuation other than hyphens and underscores removed.
+fn anchor(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|ch| match ch {
+            ' ' => Some('-'),
+            '-' | '_' => Some(ch),
+            _ if ch.is_alphanumeric() => Some(ch),
+            _ => None,
+        })
+        .collect()
+}
+
+// Headings with the same anchor are numbered, e.g. 'child', 'child-1', 'child-2'. As on
+// GitHub, the numbered anchors are taken too, so the numbering skips the anchors of
+// headings such as 'Child 1'.
+fn unique_anchor(heading: &str, anchors: &mut HashMap<String, usize>) -> String {
+    let anchor = anchor(heading);
+    let mut unique = anchor.clone();
+    while anchors.contains_key(&unique) {
+        let count = anchors.get_mut(&anchor).unwrap();
+        *count += 1;
+        unique = format!("{}-{}", anchor, count);
+    }
+    anchors.insert(unique.clone(), 0);
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::new(
+                "Root 1",
+                vec![
+                    TreeNode::new("Child", vec![TreeNode::from_label("Grandchild")]),
+                    TreeNode::from_label("Child\\nQuota: 100"),
+                ],
+            ),
+            TreeNode::from_label("Root 2"),
+        ]
+    }
+
+    #[test]
+    fn test_render_headings() {
+        assert_canonical_eq(
+            r#"
+            # Root 1
+            ## Child
+            ### Grandchild
+            ## Child
+            Quota: 100
+            # Root 2
+            "#,
+            &render_headings(&tree()),
+        )
+    }
+
+    #[test]
+    fn test_render_headings_round_trip() {
+        let roots = tree();
+        assert_eq!(parse(&render_headings(&roots), None), roots);
+    }
+
+    #[test]
+    fn test_render_list() {
+        assert_eq!(
+            render_list(&tree()),
+            [
+                "- Root 1",
+                "  - Child",
+                "    - Grandchild",
+                "  - Child\\",
+                "    Quota: 100",
+                "- Root 2",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_render_toc() {
+        assert_eq!(
+            render_toc(&tree()),
+            [
+                "- [Root 1](#root-1)",
+                "  - [Child](#child)",
+                "    - [Grandchild](#grandchild)",
+                "  - [Child](#child-1)",
+                "- [Root 2](#root-2)",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_unique_anchor() {
+        let mut anchors = HashMap::new();
+        let unique: Vec<String> = ["Child 1", "Child", "Child", "Child"]
+            .iter()
+            .map(|heading| unique_anchor(heading, &mut anchors))
+            .collect();
+        assert_eq!(unique, vec!["child-1", "child", "child-2", "child-3"]);
+    }
+
+    #[test]
+    fn test_anchor() {
+        assert_eq!(anchor("Hello, World!"), "hello-world");
+        assert_eq!(anchor("Input From File"), "input-from-file");
+        assert_eq!(anchor("snake_case & kebab-case"), "snake_case--kebab-case");
+    }
+}
@@ -0,0 +1,157 @@
+use crate::tree::tree_node::TreeNode;
+use crate::writer::Direction;
+
+// Quote a label as a Mermaid string. Characters that Mermaid would otherwise interpret
+// are written as entity codes, and lines are separated with '<br>'.
+fn quote_label(node: &TreeNode) -> String {
+    let lines: Vec<String> = node
+        .label_lines()
+        .iter()
+        .map(|line| {
+            let mut escaped = String::with_capacity(line.len());
+            for ch in line.chars() {
+                match ch {
+                    '#' => escaped.push_str("#35;"),
+                    '"' => escaped.push_str("#quot;"),
+                    '&' => escaped.push_str("#amp;"),
+                    '<' => escaped.push_str("#lt;"),
+                    '>' => escaped.push_str("#gt;"),
+                    _ => escaped.push(ch),
+                }
+            }
+            escaped
+        })
+        .collect();
+    format!("[\"{}\"]", lines.join("<br>"))
+}
+
+// Render the forest as a Mermaid flowchart, e.g.
+// graph TD
+//     n0["Root"]
+//     n0 --> n0_0["Child"]
+pub fn render_graph(roots: &[TreeNode], direction: Direction) -> String {
+    fn render_children(children: &[TreeNode], parent_id: &str, output: &mut String) {
+        for (idx, child) in children.iter().enumerate() {
+            let id = format!("{}_{}", parent_id, idx);
+            output.push_str(&format!(
+                "    {} --> {}{}\n",
+                parent_id,
+                id,
+                quote_label(child)
+            ));
+            render_children(&child.children, &id, output);
+        }
+    }
+
+    let mut output = match direction {
+        Direction::TopToBottom => "graph TD\n".to_string(),
+        Direction::LeftToRight => "graph LR\n".to_string(),
+    };
+    for (idx, root) in roots.iter().enumerate() {
+        let id = format!("n{}", idx);
+        output.push_str(&format!("    {}{}\n", id, quote_label(root)));
+        render_children(&root.children, &id, &mut output);
+    }
+    output
+}
+
+// Render the forest as a Mermaid mind map, where the nesting is defined by indentation, e.g.
+// mindmap
+//   n0["Root"]
+//     n0_0["Child"]
+//
+// A mind map can only have a single root, so if there are more than one root nodes, an
+// artificial dot is added as the global root, same as the horizontal tree.
+pub fn render_mindmap(roots: &[TreeNode]) -> String {
+    fn render_nodes(nodes: &[TreeNode], prefix: &str, indent: &str, output: &mut String) {
+        for (idx, node) in nodes.iter().enumerate() {
+            let id = format!("{}{}", prefix, idx);
+            output.push_str(&format!("{}{}{}\n", indent, id, quote_label(node)));
+            render_nodes(
+                &node.children,
+                &format!("{}_", id),
+                &format!("{}  ", indent),
+                output,
+            );
+        }
+    }
+
+    let mut output = "mindmap\n".to_string();
+    if roots.len() > 1 {
+        output.push_str("  root[\".\"]\n");
+        render_nodes(roots, "n", "    ", &mut output);
+    } else {
+        render_nodes(roots, "n", "  ", &mut output);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new("Child 1", vec![TreeNode::from_label("Grandchild")]),
+                TreeNode::from_label("Child \"2\"\\n<#1>"),
+            ],
+        )]
+    }
+
+    #[test]
+    fn test_render_graph() {
+        assert_canonical_eq(
+            r#"
+            graph TD
+                n0["Root"]
+                n0 --> n0_0["Child 1"]
+                n0_0 --> n0_0_0["Grandchild"]
+                n0 --> n0_1["Child #quot;2#quot;<br>#lt;#35;1#gt;"]
+            "#,
+            &render_graph(&tree(), Direction::TopToBottom),
+        )
+    }
+
+    #[test]
+    fn test_render_graph_left_to_right() {
+        assert!(render_graph(&tree(), Direction::LeftToRight).starts_with("graph LR\n"));
+    }
+
+    #[test]
+    fn test_render_mindmap() {
+        assert_eq!(
+            render_mindmap(&tree()),
+            [
+                "mindmap",
+                "  n0[\"Root\"]",
+                "    n0_0[\"Child 1\"]",
+                "      n0_0_0[\"Grandchild\"]",
+                "    n0_1[\"Child #quot;2#quot;<br>#lt;#35;1#gt;\"]",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_render_mindmap_multiple_roots() {
+        let roots = vec![
+            TreeNode::from_label("Root 1"),
+            TreeNode::from_label("Root 2"),
+        ];
+        assert_eq!(
+            render_mindmap(&roots),
+            [
+                "mindmap",
+                "  root[\".\"]",
+                "    n0[\"Root 1\"]",
+                "    n1[\"Root 2\"]",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+}
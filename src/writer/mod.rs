@@ -0,0 +1,193 @@
+pub mod csv;
+pub mod dot;
+pub mod drawio;
+mod font;
+pub mod html;
+pub mod json;
+pub mod latex;
+pub mod layout;
+pub mod markdown;
+pub mod mermaid;
+pub mod outline;
+pub mod plantuml;
+pub mod png;
+pub mod svg;
+pub mod template;
+
+use crate::tree::style::Style;
+use crate::tree::tree_node::TreeNode;
+use crate::writer::template::Template;
+use std::path::Path;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Draw the tree with box-drawing characters
+    Text,
+    /// A PNG image of the text output, drawn with a built-in bitmap font
+    Png,
+    /// An SVG image of the text output
+    Svg,
+    /// A self-contained HTML page with collapsible subtrees
+    Html,
+    /// The parsed tree as JSON, with a label and a list of children for each node
+    Json,
+    /// JSON Lines with one node per line, including its path and depth
+    Jsonl,
+    /// The geometry of the vertical tree as JSON, i.e. the boxes and the connectors
+    Layout,
+    /// Graphviz DOT
+    Dot,
+    /// draw.io (diagrams.net) diagram, positioned like the vertical tree
+    Drawio,
+    /// Mermaid flowchart
+    Mermaid,
+    /// Mermaid mind map
+    MermaidMindmap,
+    /// PlantUML work breakdown structure
+    PlantumlWbs,
+    /// PlantUML mind map
+    PlantumlMindmap,
+    /// LaTeX with the forest package
+    Latex,
+    /// Markdown headings, the same format as the input
+    Markdown,
+    /// Markdown nested bullet list
+    MarkdownList,
+    /// Markdown table of contents, linking to the headings
+    MarkdownToc,
+    /// Plain text, with each level indented by --indent
+    Outline,
+    /// Numbered outline, e.g. 1., 1.1., 1.1.1.
+    Numbered,
+    /// Tab-indented text, for pasting into spreadsheets and outliners
+    Tabs,
+    /// CSV edge list, with the id, parent id, depth and label of each node
+    Csv,
+    /// CSV with one row per leaf, and the labels from the root in consecutive columns
+    CsvPaths,
+    /// Custom format, with the --pre and --post templates expanded for each node
+    Template,
+}
+
+impl Format {
+    // The format matching the extension of an output file, if any
+    pub fn from_extension(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "txt" => Some(Format::Text),
+            "png" => Some(Format::Png),
+            "svg" => Some(Format::Svg),
+            "html" | "htm" => Some(Format::Html),
+            "json" => Some(Format::Json),
+            "jsonl" => Some(Format::Jsonl),
+            "csv" => Some(Format::Csv),
+            "dot" | "gv" => Some(Format::Dot),
+            "drawio" => Some(Format::Drawio),
+            "tex" => Some(Format::Latex),
+            "md" => Some(Format::Markdown),
+            _ => None,
+        }
+    }
+}
+
+// The direction in which the tree grows, from the root to the leaves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    TopToBottom,
+    LeftToRight,
+}
+
+// The settings from the command line that some of the formats depend on
+#[derive(Debug, Clone)]
+pub struct Options {
+    // A hint for the formats that support it, such as the rank direction of Graphviz
+    pub direction: Direction,
+    // The style from --style, which the formats with borders resemble
+    pub style: Style,
+    // Whether the nodes of the LaTeX format have borders
+    pub latex_borders: bool,
+    // The indent of each level of the outline formats
+    pub indent: String,
+    // The templates expanded before and after the children of each node
+    pub pre: Template,
+    pub post: Template,
+}
+
+// Render the formats that only depend on the structure of the tree, rather than on
+// how it is drawn.
+pub fn render(format: Format, roots: &[TreeNode], options: &Options) -> String {
+    match format {
+        Format::Html => html::render(roots),
+        Format::Json => json::render(roots),
+        Format::Jsonl => json::render_lines(roots),
+        Format::Dot => dot::render(roots, options.direction, options.style),
+        Format::Mermaid => mermaid::render_graph(roots, options.direction),
+        Format::MermaidMindmap => mermaid::render_mindmap(roots),
+        Format::PlantumlWbs => plantuml::render_wbs(roots),
+        Format::PlantumlMindmap => plantuml::render_mindmap(roots),
+        Format::Latex => latex::render(
+            roots,
+            options.direction,
+            options.latex_borders.then_some(options.style),
+        ),
+        Format::Markdown => markdown::render_headings(roots),
+        Format::MarkdownList => markdown::render_list(roots),
+        Format::MarkdownToc => markdown::render_toc(roots),
+        Format::Outline => outline::render_indented(roots, &options.indent),
+        Format::Numbered => outline::render_numbered(roots, &options.indent),
+        Format::Tabs => outline::render_tabs(roots),
+        Format::Csv => csv::render_edges(roots),
+        Format::CsvPaths => csv::render_paths(roots),
+        Format::Template => template::render(roots, &options.pre, &options.post, &options.indent),
+        Format::Text | Format::Png | Format::Svg | Format::Layout | Format::Drawio => {
+            unreachable!("{:?} is drawn by the tree renderers", format)
+        }
+    }
+}
+
+// Escape the characters that are special in HTML and XML text and attribute values
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            Format::from_extension(Path::new("docs/tree.svg")),
+            Some(Format::Svg)
+        );
+        assert_eq!(
+            Format::from_extension(Path::new("TREE.HTML")),
+            Some(Format::Html)
+        );
+        assert_eq!(
+            Format::from_extension(Path::new("tree.md")),
+            Some(Format::Markdown)
+        );
+        assert_eq!(Format::from_extension(Path::new("tree")), None);
+        assert_eq!(Format::from_extension(Path::new("tree.xyz")), None);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::tree::tree_node::TreeNode;
+
+// Render the forest as plain text, with each level indented by `indent`, e.g.
+// Root
+//   Child
+//     Grandchild
+//
+// Multi-line labels are joined with spaces, as their following lines would otherwise
+// read as siblings.
+pub fn render_indented(roots: &[TreeNode], indent: &str) -> String {
+    fn render_nodes(nodes: &[TreeNode], prefix: &str, indent: &str, output: &mut String) {
+        for node in nodes {
+            let label = node.label_lines().join(" ");
+            output.push_str(&format!("{}{}\n", prefix, label));
+            render_nodes(
+                &node.children,
+                &format!("{}{}", prefix, indent),
+                indent,
+                output,
+            );
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, "", indent, &mut output);
+    output
+}
+
+// Render the forest as a numbered outline, with each level indented by `indent`, e.g.
+// 1. Root
+//   1.1. Child
+//     1.1.1. Grandchild
+//
+// The following lines of a multi-line label are aligned with the first one.
+pub fn render_numbered(roots: &[TreeNode], indent: &str) -> String {
+    fn render_nodes(
+        nodes: &[TreeNode],
+        prefix: &str,
+        number: &str,
+        indent: &str,
+        output: &mut String,
+    ) {
+        for (idx, node) in nodes.iter().enumerate() {
+            let number = format!("{}{}.", number, idx + 1);
+            let lines = node.label_lines();
+            output.push_str(&format!("{}{} {}\n", prefix, number, lines[0]));
+            let alignment = " ".repeat(number.len() + 1);
+            for line in &lines[1..] {
+                output.push_str(&format!("{}{}{}\n", prefix, alignment, line));
+            }
+            let prefix = format!("{}{}", prefix, indent);
+            render_nodes(&node.children, &prefix, &number, indent, output);
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, "", "", indent, &mut output);
+    output
+}
+
+// Render the forest with one node per line, indented with tabs, which spreadsheets
+// and outliners paste as one row per node with the depth as the column. Multi-line
+// labels are joined with spaces, to keep one node per line.
+pub fn render_tabs(roots: &[TreeNode]) -> String {
+    fn render_nodes(nodes: &[TreeNode], depth: usize, output: &mut String) {
+        for node in nodes {
+            let label = node.label_lines().join(" ");
+            output.push_str(&format!("{}{}\n", "\t".repeat(depth), label));
+            render_nodes(&node.children, depth + 1, output);
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, 0, &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::new(
+                "Root 1",
+                vec![
+                    TreeNode::new("Child", vec![TreeNode::from_label("Grandchild")]),
+                    TreeNode::from_label("Child\\nQuota: 100"),
+                ],
+            ),
+            TreeNode::from_label("Root 2"),
+        ]
+    }
+
+    #[test]
+    fn test_render_indented() {
+        assert_eq!(
+            render_indented(&tree(), "  "),
+            [
+                "Root 1",
+                "  Child",
+                "    Grandchild",
+                "  Child Quota: 100",
+                "Root 2",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_render_numbered() {
+        assert_eq!(
+            render_numbered(&tree(), "    "),
+            [
+                "1. Root 1",
+                "    1.1. Child",
+                "        1.1.1. Grandchild",
+                "    1.2. Child",
+                "         Quota: 100",
+                "2. Root 2",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_render_tabs() {
+        assert_eq!(
+            render_tabs(&tree()),
+            "Root 1\n\tChild\n\t\tGrandchild\n\tChild Quota: 100\nRoot 2\n"
+        )
+    }
+}
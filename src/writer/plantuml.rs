@@ -0,0 +1,141 @@
+use crate::tree::tree_node::TreeNode;
+
+// Escape the Creole markup in a line, e.g. '**' for bold or '--' for strike-through,
+// with the escape character '~'. HTML-like tags such as '<b>' are escaped as well.
+fn escape_line(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut escaped = String::with_capacity(line.len());
+    for (idx, &ch) in chars.iter().enumerate() {
+        let doubled = chars.get(idx + 1) == Some(&ch) || (idx > 0 && chars[idx - 1] == ch);
+        if ch == '<' || (doubled && "*/\"-_~".contains(ch)) {
+            escaped.push('~');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+// A single line label is written as is, while multi-line labels are written as a
+// block between ':' and ';'.
+//
+// * Root
+// **:Child
+// Quota: 100;
+fn label(node: &TreeNode) -> String {
+    let lines: Vec<String> = node
+        .label_lines()
+        .iter()
+        .map(|line| escape_line(line))
+        .collect();
+    if lines.len() == 1 {
+        format!(" {}", lines[0])
+    } else {
+        // A line ending with ';' would close the block early
+        let lines: Vec<String> = lines
+            .iter()
+            .map(|line| match line.strip_suffix(';') {
+                Some(stripped) => format!("{}~;", stripped),
+                None => line.to_string(),
+            })
+            .collect();
+        format!(":{};", lines.join("\n"))
+    }
+}
+
+fn render_nodes(nodes: &[TreeNode], depth: usize, output: &mut String) {
+    for node in nodes {
+        output.push_str(&format!("{}{}\n", "*".repeat(depth), label(node)));
+        render_nodes(&node.children, depth + 1, output);
+    }
+}
+
+// Both the work breakdown structure and the mind map can only have a single root, so if
+// there are more than one root nodes, an artificial dot is added as the global root,
+// same as the horizontal tree.
+fn render(roots: &[TreeNode], start: &str, end: &str) -> String {
+    let mut output = format!("{}\n", start);
+    if roots.len() > 1 {
+        output.push_str("* .\n");
+        render_nodes(roots, 2, &mut output);
+    } else {
+        render_nodes(roots, 1, &mut output);
+    }
+    output.push_str(&format!("{}\n", end));
+    output
+}
+
+// Render the forest as a PlantUML work breakdown structure, e.g.
+// @startwbs
+// * Root
+// ** Child
+// @endwbs
+pub fn render_wbs(roots: &[TreeNode]) -> String {
+    render(roots, "@startwbs", "@endwbs")
+}
+
+// Render the forest as a PlantUML mind map, e.g.
+// @startmindmap
+// * Root
+// ** Child
+// @endmindmap
+pub fn render_mindmap(roots: &[TreeNode]) -> String {
+    render(roots, "@startmindmap", "@endmindmap")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_escape_line() {
+        assert_eq!(escape_line("a-b"), "a-b");
+        assert_eq!(escape_line("a--b"), "a~-~-b");
+        assert_eq!(escape_line("**bold**"), "~*~*bold~*~*");
+        assert_eq!(escape_line("<b>"), "~<b>");
+    }
+
+    #[test]
+    fn test_render_wbs() {
+        let roots = vec![TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new("Child 1", vec![TreeNode::from_label("Grandchild")]),
+                TreeNode::from_label("Child 2\\nQuota: 100"),
+            ],
+        )];
+
+        assert_canonical_eq(
+            r#"
+            @startwbs
+            * Root
+            ** Child 1
+            *** Grandchild
+            **:Child 2
+            Quota: 100;
+            @endwbs
+            "#,
+            &render_wbs(&roots),
+        )
+    }
+
+    #[test]
+    fn test_render_mindmap_multiple_roots() {
+        let roots = vec![
+            TreeNode::new("Root 1", vec![TreeNode::from_label("Child 1")]),
+            TreeNode::from_label("Root 2"),
+        ];
+
+        assert_canonical_eq(
+            r#"
+            @startmindmap
+            * .
+            ** Root 1
+            *** Child 1
+            ** Root 2
+            @endmindmap
+            "#,
+            &render_mindmap(&roots),
+        )
+    }
+}
@@ -0,0 +1,324 @@
+use crate::writer::font;
+use crate::writer::font::{Arms, GLYPH_HEIGHT, GLYPH_WIDTH};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    // Parse a hex color such as '#336699' or '336699'
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let hex = text.strip_prefix('#').unwrap_or(text);
+        let component = |idx: usize| hex.get(idx..idx + 2).map(|c| u8::from_str_radix(c, 16));
+        match (hex.len(), component(0), component(2), component(4)) {
+            (6, Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => Ok(Rgb(r, g, b)),
+            _ => Err(format!("'{}' is not a hex color such as '#336699'", text)),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct PngOptions {
+    /// The width of each character in the png image, in pixels
+    #[clap(long, default_value_t = 12)]
+    pub cell_width: usize,
+
+    /// The height of each character in the png image, in pixels
+    #[clap(long, default_value_t = 24)]
+    pub cell_height: usize,
+
+    /// The color of the text and the lines in the png image
+    #[clap(long, default_value = "#000000")]
+    pub foreground: Rgb,
+
+    /// The color of the background of the png image
+    #[clap(long, default_value = "#ffffff")]
+    pub background: Rgb,
+
+    /// The margin around the tree in the png image, in pixels
+    #[clap(long, default_value_t = 12)]
+    pub padding: usize,
+}
+
+// An RGB image with 3 bytes per pixel, row by row
+#[derive(Debug)]
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: usize, height: usize, background: Rgb) -> Image {
+        let pixels = [background.0, background.1, background.2].repeat(width * height);
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    #[cfg(test)]
+    fn pixel(&self, x: usize, y: usize) -> Rgb {
+        let idx = (y * self.width + x) * 3;
+        Rgb(self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2])
+    }
+
+    // Fill the pixels in [x_start, x_end) x [y_start, y_end)
+    fn fill(&mut self, x_start: usize, x_end: usize, y_start: usize, y_end: usize, color: Rgb) {
+        for y in y_start..y_end.min(self.height) {
+            for x in x_start..x_end.min(self.width) {
+                let idx = (y * self.width + x) * 3;
+                self.pixels[idx..idx + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+}
+
+// Draw the character grid, i.e. the text output of the tree, with the built-in font.
+// Each character takes exactly one cell, so the boxes and lines line up as they do in
+// a terminal.
+fn rasterize(text: &str, options: &PngOptions) -> Image {
+    // The cells are at least large enough for the glyphs plus one pixel of spacing
+    let cell_width = options.cell_width.max(GLYPH_WIDTH + 1);
+    let cell_height = options.cell_height.max(GLYPH_HEIGHT + 1);
+
+    let rows: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let width = (columns * cell_width + 2 * options.padding).max(1);
+    let height = (rows.len() * cell_height + 2 * options.padding).max(1);
+
+    let mut image = Image::new(width, height, options.background);
+    for (row_idx, row) in rows.iter().enumerate() {
+        for (column_idx, &ch) in row.iter().enumerate() {
+            let cell = Cell {
+                x: options.padding + column_idx * cell_width,
+                y: options.padding + row_idx * cell_height,
+                width: cell_width,
+                height: cell_height,
+            };
+            if let Some(arms) = font::arms(ch) {
+                cell.draw_arms(&mut image, arms, options.foreground);
+            } else if ch != ' ' {
+                cell.draw_glyph(&mut image, ch, options.foreground);
+            }
+        }
+    }
+    image
+}
+
+struct Cell {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Cell {
+    // Scale the glyphs by the largest integer factor that fits in the cell
+    fn draw_glyph(&self, image: &mut Image, ch: char, color: Rgb) {
+        let scale = (self.width / (GLYPH_WIDTH + 1))
+            .min(self.height / (GLYPH_HEIGHT + 1))
+            .max(1);
+        let left = self.x + (self.width - GLYPH_WIDTH * scale) / 2;
+        let top = self.y + (self.height - GLYPH_HEIGHT * scale) / 2;
+
+        let pixels = font::glyph(ch).unwrap_or_else(|| {
+            // Draw a hollow box for the characters that are not in the font
+            let mut pixels = [[false; GLYPH_WIDTH]; GLYPH_HEIGHT];
+            for (y, row) in pixels.iter_mut().enumerate().take(7) {
+                for (x, pixel) in row.iter_mut().enumerate() {
+                    *pixel = y == 0 || y == 6 || x == 0 || x == GLYPH_WIDTH - 1;
+                }
+            }
+            pixels
+        });
+
+        for (y, row) in pixels.iter().enumerate() {
+            for (x, &on) in row.iter().enumerate() {
+                if on {
+                    let px = left + x * scale;
+                    let py = top + y * scale;
+                    image.fill(px, px + scale, py, py + scale, color);
+                }
+            }
+        }
+    }
+
+    fn draw_arms(&self, image: &mut Image, arms: Arms, color: Rgb) {
+        for bar in arms.bars(self.width, self.height) {
+            image.fill(
+                self.x + bar.x_start,
+                self.x + bar.x_end,
+                self.y + bar.y_start,
+                self.y + bar.y_end,
+                color,
+            );
+        }
+    }
+}
+
+// Render the character grid as a PNG image
+pub fn render(text: &str, options: &PngOptions) -> Vec<u8> {
+    let image = rasterize(text, options);
+    encode(&image)
+}
+
+// Encode the image as an 8-bit RGB PNG. The image data is stored without compression,
+// which is still a valid zlib stream and keeps the encoder small.
+fn encode(image: &Image) -> Vec<u8> {
+    let mut output = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    let mut header = vec![];
+    header.extend_from_slice(&(image.width as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height as u32).to_be_bytes());
+    // Bit depth 8, color type RGB, default compression, filter and no interlace
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(&mut output, b"IHDR", &header);
+
+    // Each scanline starts with the filter type, which is none
+    let mut scanlines = Vec::with_capacity((image.width * 3 + 1) * image.height);
+    for row in image.pixels.chunks(image.width * 3) {
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+
+    // The zlib header for deflate with a 32K window and no preset dictionary
+    let mut data = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = scanlines.chunks(u16::MAX as usize).collect();
+    for (idx, block) in blocks.iter().enumerate() {
+        // Stored block, with the final bit set on the last block
+        data.push((idx == blocks.len() - 1) as u8);
+        let len = block.len() as u16;
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(&(!len).to_le_bytes());
+        data.extend_from_slice(block);
+    }
+    data.extend_from_slice(&adler32(&scanlines).to_be_bytes());
+    write_chunk(&mut output, b"IDAT", &data);
+
+    write_chunk(&mut output, b"IEND", &[]);
+    output
+}
+
+fn write_chunk(output: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = output.len();
+    output.extend_from_slice(kind);
+    output.extend_from_slice(data);
+    let crc = crc32(&output[start..]);
+    output.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+        }
+        *entry = c;
+    }
+
+    let mut crc = 0xffffffff;
+    for &byte in bytes {
+        crc = table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc ^ 0xffffffff
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = Rgb(0, 0, 0);
+    const WHITE: Rgb = Rgb(255, 255, 255);
+
+    fn options() -> PngOptions {
+        PngOptions {
+            cell_width: 12,
+            cell_height: 24,
+            foreground: BLACK,
+            background: WHITE,
+            padding: 2,
+        }
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!("#336699".parse(), Ok(Rgb(0x33, 0x66, 0x99)));
+        assert_eq!("FFffFF".parse(), Ok(WHITE));
+        assert!("#3369".parse::<Rgb>().is_err());
+        assert!("#33669g".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn test_rasterize_size() {
+        let image = rasterize("┌──┐\n└──┘\n", &options());
+        assert_eq!(image.width, 4 * 12 + 4);
+        assert_eq!(image.height, 2 * 24 + 4);
+    }
+
+    #[test]
+    fn test_rasterize_connected_lines() {
+        let image = rasterize("─┬─\n │ \n", &options());
+        // The horizontal line runs through the whole first row
+        for x in 2..2 + 3 * 12 {
+            assert_eq!(image.pixel(x, 2 + 12), BLACK);
+        }
+        // The vertical line runs from the center of the first row to the bottom
+        for y in 2 + 12..2 + 2 * 24 {
+            assert_eq!(image.pixel(2 + 12 + 6, y), BLACK);
+        }
+        // Nothing above the junction
+        assert_eq!(image.pixel(2 + 12 + 6, 2 + 6), WHITE);
+    }
+
+    #[test]
+    fn test_rasterize_glyph() {
+        let image = rasterize("T", &options());
+        // The glyphs are scaled by 2 and centered in the cell
+        let (left, top) = (2 + 1, 2 + 3);
+        assert_eq!(image.pixel(left, top), BLACK);
+        assert_eq!(image.pixel(left + 9, top), BLACK);
+        assert_eq!(image.pixel(left, top + 2), WHITE);
+        assert_eq!(image.pixel(left + 4, top + 12), BLACK);
+    }
+
+    #[test]
+    fn test_encode() {
+        let image = Image::new(2, 1, WHITE);
+        let png = encode(&image);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // IHDR with a width of 2 and a height of 1
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 2, 0, 0, 0, 1]);
+        // The image data is the filter type followed by the pixels
+        assert_eq!(&png[37..41], b"IDAT");
+        assert_eq!(&png[43..48], &[1, 7, 0, 0xf8, 0xff]);
+        assert_eq!(&png[48..55], &[0, 255, 255, 255, 255, 255, 255]);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+}
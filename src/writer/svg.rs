@@ -0,0 +1,112 @@
+use crate::writer::escape_xml;
+use crate::writer::font;
+
+// The size of each character, in pixels
+const CELL_WIDTH: usize = 9;
+const CELL_HEIGHT: usize = 18;
+
+const FONT_SIZE: usize = 15;
+
+// The distance from the top of a character to the baseline of the text
+const BASELINE: usize = 13;
+
+// Draw the character grid, i.e. the text output of the tree, as an SVG image. The text
+// is written with a monospace font, while the box-drawing characters are drawn as
+// filled rectangles so that they are connected regardless of the font, e.g.
+// <text x="9" y="31" textLength="36">Root</text>
+// <path d="M4 27h1v9h-1z ..."/>
+pub fn render(text: &str) -> String {
+    let rows: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let width = columns * CELL_WIDTH;
+    let height = rows.len() * CELL_HEIGHT;
+
+    let mut texts = String::new();
+    let mut path: Vec<String> = vec![];
+    for (row_idx, row) in rows.iter().enumerate() {
+        let top = row_idx * CELL_HEIGHT;
+
+        // Runs of consecutive characters that are not box-drawing characters
+        let mut runs: Vec<(usize, String)> = vec![];
+        let mut run: Option<(usize, String)> = None;
+        for (column_idx, &ch) in row.iter().enumerate() {
+            match font::arms(ch) {
+                Some(arms) => {
+                    runs.extend(run.take());
+                    let left = column_idx * CELL_WIDTH;
+                    for bar in arms.bars(CELL_WIDTH, CELL_HEIGHT) {
+                        path.push(format!(
+                            "M{} {}h{}v{}h-{}z",
+                            left + bar.x_start,
+                            top + bar.y_start,
+                            bar.x_end - bar.x_start,
+                            bar.y_end - bar.y_start,
+                            bar.x_end - bar.x_start
+                        ));
+                    }
+                }
+                None => run.get_or_insert((column_idx, String::new())).1.push(ch),
+            }
+        }
+        runs.extend(run.take());
+
+        for (start, run) in runs {
+            let trimmed = run.trim_start();
+            let start = start + (run.chars().count() - trimmed.chars().count());
+            let trimmed = trimmed.trim_end();
+            if trimmed.is_empty() {
+                continue;
+            }
+            texts.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\" textLength=\"{}\">{}</text>\n",
+                start * CELL_WIDTH,
+                top + BASELINE,
+                trimmed.chars().count() * CELL_WIDTH,
+                escape_xml(trimmed)
+            ));
+        }
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        width, height, width, height
+    ));
+    output.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    output.push_str(&format!(
+        "  <g font-family=\"monospace\" font-size=\"{}\" fill=\"black\" xml:space=\"preserve\">\n",
+        FONT_SIZE
+    ));
+    output.push_str(&texts);
+    output.push_str("  </g>\n");
+    if !path.is_empty() {
+        output.push_str(&format!(
+            "  <path fill=\"black\" d=\"{}\"/>\n",
+            path.join("")
+        ));
+    }
+    output.push_str("</svg>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_canonical_eq;
+
+    #[test]
+    fn test_render() {
+        assert_canonical_eq(
+            r#"
+            <svg xmlns="http://www.w3.org/2000/svg" width="27" height="36" viewBox="0 0 27 36">
+              <rect width="100%" height="100%" fill="white"/>
+              <g font-family="monospace" font-size="15" fill="black" xml:space="preserve">
+                <text x="0" y="13" textLength="9">a</text>
+              </g>
+              <path fill="black" d="M9 9h5v1h-5zM13 9h5v1h-5zM22 9h1v9h-1zM18 9h5v1h-5zM22 18h1v10h-1zM22 27h1v9h-1z"/>
+            </svg>
+            "#,
+            &render("a─┐\n  │\n"),
+        )
+    }
+}
@@ -0,0 +1,267 @@
+use crate::tree::tree_node::TreeNode;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    // The label, with the lines of a multi-line label joined with spaces
+    Label,
+    // The number of ancestors, i.e. 0 for the roots
+    Depth,
+    // The position among the siblings, starting from 0
+    Index,
+    // The labels from the root to the node, joined with '/'
+    Path,
+    // Whether the node is the last of its siblings, i.e. 'true' or 'false'
+    IsLast,
+    ChildCount,
+    // The indent repeated `depth` times
+    Indent,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Placeholder> {
+        match name {
+            "label" => Some(Placeholder::Label),
+            "depth" => Some(Placeholder::Depth),
+            "index" => Some(Placeholder::Index),
+            "path" => Some(Placeholder::Path),
+            "is_last" => Some(Placeholder::IsLast),
+            "child_count" => Some(Placeholder::ChildCount),
+            "indent" => Some(Placeholder::Indent),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+// A template such as '{indent}- {label}\n', where the placeholders in braces are
+// replaced with the properties of each node. Use '{{' and '}}' for literal braces, and
+// '\n' and '\t' for line breaks and tabs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match (ch, chars.peek()) {
+                ('\\', Some('n')) => {
+                    chars.next();
+                    literal.push('\n');
+                }
+                ('\\', Some('t')) => {
+                    chars.next();
+                    literal.push('\t');
+                }
+                ('\\', Some('\\')) => {
+                    chars.next();
+                    literal.push('\\');
+                }
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    literal.push(ch);
+                }
+                ('{', _) => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => name.push(ch),
+                            None => return Err(format!("unclosed placeholder '{{{}'", name)),
+                        }
+                    }
+                    let placeholder = Placeholder::from_name(&name).ok_or(format!(
+                        "unknown placeholder '{{{}}}', expected one of {{label}}, {{depth}}, {{index}}, {{path}}, {{is_last}}, {{child_count}} or {{indent}}",
+                        name
+                    ))?;
+                    if !literal.is_empty() {
+                        segments.push(Segment::Text(literal.clone()));
+                        literal.clear();
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                }
+                _ => literal.push(ch),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Text(literal));
+        }
+        Ok(Template { segments })
+    }
+}
+
+// The properties of a node that are available to the templates
+struct Context<'a> {
+    node: &'a TreeNode,
+    depth: usize,
+    index: usize,
+    is_last: bool,
+    path: &'a [String],
+    indent: &'a str,
+}
+
+impl Template {
+    fn expand(&self, context: &Context, output: &mut String) {
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Placeholder(placeholder) => {
+                    let value = match placeholder {
+                        Placeholder::Label => context.node.label_lines().join(" "),
+                        Placeholder::Depth => context.depth.to_string(),
+                        Placeholder::Index => context.index.to_string(),
+                        Placeholder::Path => context.path.join("/"),
+                        Placeholder::IsLast => context.is_last.to_string(),
+                        Placeholder::ChildCount => context.node.children.len().to_string(),
+                        Placeholder::Indent => context.indent.repeat(context.depth),
+                    };
+                    output.push_str(&value);
+                }
+            }
+        }
+    }
+}
+
+// Render the forest by expanding the `pre` template before the children of each node,
+// and the `post` template after them, e.g. with pre '{indent}<li>{label}\n' and post
+// '{indent}</li>\n'. The labels are inserted as is, without any escaping, so a
+// label with quotes gives an invalid string literal in a code template.
+pub fn render(roots: &[TreeNode], pre: &Template, post: &Template, indent: &str) -> String {
+    fn render_nodes(
+        nodes: &[TreeNode],
+        depth: usize,
+        path: &mut Vec<String>,
+        templates: (&Template, &Template, &str),
+        output: &mut String,
+    ) {
+        let (pre, post, indent) = templates;
+        for (index, node) in nodes.iter().enumerate() {
+            path.push(node.label_lines().join(" "));
+            let is_last = index == nodes.len() - 1;
+            let context = Context {
+                node,
+                depth,
+                index,
+                is_last,
+                path,
+                indent,
+            };
+            pre.expand(&context, output);
+            render_nodes(&node.children, depth + 1, path, templates, output);
+            let context = Context {
+                node,
+                depth,
+                index,
+                is_last,
+                path,
+                indent,
+            };
+            post.expand(&context, output);
+            path.pop();
+        }
+    }
+
+    let mut output = String::new();
+    render_nodes(roots, 0, &mut vec![], (pre, post, indent), &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<TreeNode> {
+        vec![TreeNode::new(
+            "Root",
+            vec![
+                TreeNode::new("Child 1", vec![TreeNode::from_label("Grandchild")]),
+                TreeNode::from_label("Child 2\\nQuota: 100"),
+            ],
+        )]
+    }
+
+    fn template(text: &str) -> Template {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            template("{{{label}}}\\n"),
+            Template {
+                segments: vec![
+                    Segment::Text("{".to_string()),
+                    Segment::Placeholder(Placeholder::Label),
+                    Segment::Text("}\n".to_string()),
+                ]
+            }
+        );
+        assert!("{name}".parse::<Template>().is_err());
+        assert!("{label".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_render_jira_list() {
+        assert_eq!(
+            render(
+                &tree(),
+                &template("{indent}* {label}\\n"),
+                &template(""),
+                "*"
+            ),
+            [
+                "* Root",
+                "** Child 1",
+                "*** Grandchild",
+                "** Child 2 Quota: 100",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+
+    #[test]
+    fn test_render_initializer_list() {
+        assert_eq!(
+            render(
+                &tree(),
+                &template("{{\"{label}\", {{"),
+                &template("}}}}{is_last}, "),
+                ""
+            ),
+            "{\"Root\", {{\"Child 1\", {{\"Grandchild\", {}}true, }}false, \
+             {\"Child 2 Quota: 100\", {}}true, }}true, "
+        )
+    }
+
+    #[test]
+    fn test_render_path_and_counts() {
+        assert_eq!(
+            render(
+                &tree(),
+                &template("{path} {depth} {index} {child_count}\\n"),
+                &template(""),
+                ""
+            ),
+            [
+                "Root 0 0 2",
+                "Root/Child 1 1 0 1",
+                "Root/Child 1/Grandchild 2 0 0",
+                "Root/Child 2 Quota: 100 1 1 0",
+                "",
+            ]
+            .join("\n")
+        )
+    }
+}